@@ -0,0 +1,169 @@
+use ortalab::scoring::card::hand_eval::HandAnalysis;
+use ortalab::scoring::card::playing_card::{Card, Enhancement};
+use ortalab::scoring::joker::joker_card::JokerCard;
+use ortalab::scoring::joker::joker_util::set_joker_effects;
+use ortalab::scoring::round::Round;
+use ortalab::scoring::scorer::calculate_score_in_place;
+use ortalab::scoring::trace::ScoreTrace;
+use ortalib::{Rank, Suit};
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+// File contains the scoring benchmark, run with `cargo bench`.
+// it times hand detection and full scoring over a fixed set of random rounds and..
+// counts heap allocations, which should be zero for both
+
+// counts every allocation made through the global allocator
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+const ROUNDS: usize = 1000;
+const RUN_TIME: Duration = Duration::from_secs(2);
+
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+
+const JOKERS: [&str; 16] = [
+    "Jolly Joker",
+    "Mad Joker",
+    "Crafty Joker",
+    "Abstract Joker",
+    "Four Fingers",
+    "Shortcut",
+    "Smeared Joker",
+    "Blueprint",
+    "Brainstorm",
+    "Photograph",
+    "Raised Fist",
+    "Baron",
+    "Sock And Buskin",
+    "Hiker",
+    "Flower Pot",
+    "Greedy Joker",
+];
+
+// small linear congruential generator, so every run scores the same rounds
+struct Lcg(u64);
+
+impl Lcg {
+    fn below(&mut self, bound: usize) -> usize {
+        self.0 = self
+            .0
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        ((self.0 >> 33) % bound as u64) as usize
+    }
+
+    fn card(&mut self) -> Card {
+        let enhancement = match self.below(6) {
+            0 => Some(Enhancement::Wild),
+            1 => Some(Enhancement::Glass),
+            2 => Some(Enhancement::Steel),
+            _ => None,
+        };
+        Card::new(
+            RANKS[self.below(RANKS.len())],
+            SUITS[self.below(SUITS.len())],
+            enhancement,
+            None,
+        )
+    }
+}
+
+fn random_rounds() -> Vec<Round> {
+    let mut rng = Lcg(0x5eed);
+
+    (0..ROUNDS)
+        .map(|_| {
+            let played = 1 + rng.below(5);
+            let held = rng.below(4);
+            let jokers = rng.below(6);
+
+            let mut round = Round {
+                cards_played: (0..played).map(|_| rng.card()).collect(),
+                cards_held_in_hand: (0..held).map(|_| rng.card()).collect(),
+                jokers: (0..jokers)
+                    .map(|_| {
+                        JOKERS[rng.below(JOKERS.len())]
+                            .parse::<JokerCard>()
+                            .unwrap()
+                    })
+                    .collect(),
+                ..Round::default()
+            };
+            round.assign_card_ids();
+            round
+        })
+        .collect()
+}
+
+// runs pass over every round until RUN_TIME has passed, then prints rounds per second..
+// and allocations per round
+fn bench(name: &str, rounds: &[Round], mut pass: impl FnMut(&Round)) {
+    let start = Instant::now();
+    let allocations_before = ALLOCATIONS.load(Ordering::Relaxed);
+    let mut evaluations = 0;
+
+    while start.elapsed() < RUN_TIME {
+        for round in rounds {
+            pass(round);
+        }
+        evaluations += rounds.len();
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - allocations_before;
+    println!(
+        "{name:<16} {:>12.0} rounds/s  {:>6.2} allocations/round",
+        evaluations as f64 / elapsed,
+        allocations as f64 / evaluations as f64
+    );
+}
+
+fn main() {
+    let rounds = random_rounds();
+
+    bench("hand detection", &rounds, |round| {
+        let flags = set_joker_effects(&round.jokers);
+        black_box(HandAnalysis::new(black_box(&round.cards_played), &flags));
+    });
+
+    bench("scoring", &rounds, |round| {
+        black_box(calculate_score_in_place(
+            black_box(round),
+            &mut ScoreTrace::disabled(),
+        ));
+    });
+}
@@ -0,0 +1,30 @@
+/*
+ * C API of the ortalab shared library (libortalab.so / libortalab.dylib / ortalab.dll).
+ *
+ * Rounds are passed as YAML or JSON strings using the same fields as round files,
+ * and results come back as JSON strings:
+ *
+ *   {"score":1208.0,"hand":"Straight Flush","chips":151.0,"mult":8.0,"trace":[...]}
+ *   {"error":{"kind":"parse","message":"...","diagnostics":[...]}}
+ *
+ * Every string returned by the library must be released with ortalab_free_string.
+ */
+
+#ifndef ORTALAB_H
+#define ORTALAB_H
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Scores a nul-terminated UTF-8 YAML or JSON round. Never returns NULL. */
+char *ortalab_score(const char *round);
+
+/* Frees a string returned by ortalab_score. Passing NULL does nothing. */
+void ortalab_free_string(char *result);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* ORTALAB_H */
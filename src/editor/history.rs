@@ -0,0 +1,172 @@
+use crate::scoring::card::playing_card::{Card, Edition, Enhancement};
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::round::Round;
+
+// File contains the round being edited interactively, along with its undo and redo..
+// history. every edit stores the previous round, so undo is a single swap
+
+// the rows of a round that can be edited
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Row {
+    Played,
+    Held,
+    Jokers,
+}
+
+// a single change to the round being edited. positions are 0-based
+pub enum Edit {
+    AddPlayed(Vec<Card>),
+    AddHeld(Vec<Card>),
+    AddJokers(Vec<JokerCard>),
+    Remove(Row, usize),
+    // moves the card or joker at the first position to the second
+    Move(Row, usize, usize),
+    // steps a card through no enhancement, then each enhancement in turn
+    CycleEnhancement(Row, usize),
+    // steps a card or joker through no edition, then each edition in turn
+    CycleEdition(Row, usize),
+}
+
+#[derive(Default)]
+pub struct RoundHistory {
+    round: Round,
+    undo_stack: Vec<Round>,
+    redo_stack: Vec<Round>,
+}
+
+impl Row {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Row::Played => "played cards",
+            Row::Held => "held cards",
+            Row::Jokers => "jokers",
+        }
+    }
+}
+
+impl RoundHistory {
+    pub fn new(round: Round) -> Self {
+        Self {
+            round,
+            ..Self::default()
+        }
+    }
+
+    pub fn round(&self) -> &Round {
+        &self.round
+    }
+
+    pub fn row_len(&self, row: Row) -> usize {
+        match row {
+            Row::Played => self.round.cards_played.len(),
+            Row::Held => self.round.cards_held_in_hand.len(),
+            Row::Jokers => self.round.jokers.len(),
+        }
+    }
+
+    // applies edit and records the previous round for undo. a new edit clears redo
+    pub fn apply(&mut self, edit: Edit) -> Result<(), String> {
+        let mut round = self.round.clone();
+
+        match edit {
+            Edit::AddPlayed(cards) => round.cards_played.extend(cards),
+            Edit::AddHeld(cards) => round.cards_held_in_hand.extend(cards),
+            Edit::AddJokers(jokers) => round.jokers.extend(jokers),
+            Edit::Remove(row, index) => {
+                self.check_position(row, index)?;
+                match row {
+                    Row::Played => {
+                        round.cards_played.remove(index);
+                    }
+                    Row::Held => {
+                        round.cards_held_in_hand.remove(index);
+                    }
+                    Row::Jokers => {
+                        round.jokers.remove(index);
+                    }
+                }
+            }
+            Edit::Move(row, from, to) => {
+                self.check_position(row, from)?;
+                self.check_position(row, to)?;
+                match row {
+                    Row::Played => move_item(&mut round.cards_played, from, to),
+                    Row::Held => move_item(&mut round.cards_held_in_hand, from, to),
+                    Row::Jokers => move_item(&mut round.jokers, from, to),
+                }
+            }
+            Edit::CycleEnhancement(row, index) => {
+                self.check_position(row, index)?;
+                let card = match row {
+                    Row::Played => &mut round.cards_played[index],
+                    Row::Held => &mut round.cards_held_in_hand[index],
+                    Row::Jokers => return Err("Jokers don't have enhancements".to_string()),
+                };
+                card.enhancement = next_option(&Enhancement::ALL, card.enhancement);
+            }
+            Edit::CycleEdition(row, index) => {
+                self.check_position(row, index)?;
+                let edition = match row {
+                    Row::Played => &mut round.cards_played[index].edition,
+                    Row::Held => &mut round.cards_held_in_hand[index].edition,
+                    Row::Jokers => &mut round.jokers[index].edition,
+                };
+                *edition = next_option(&Edition::ALL, *edition);
+            }
+        }
+
+        self.undo_stack
+            .push(std::mem::replace(&mut self.round, round));
+        self.redo_stack.clear();
+        Ok(())
+    }
+
+    fn check_position(&self, row: Row, index: usize) -> Result<(), String> {
+        if index >= self.row_len(row) {
+            return Err(format!(
+                "There is no position {} in the {}, which have {}",
+                index + 1,
+                row.name(),
+                self.row_len(row)
+            ));
+        }
+        Ok(())
+    }
+
+    // returns false if there is nothing to undo
+    pub fn undo(&mut self) -> bool {
+        let Some(previous) = self.undo_stack.pop() else {
+            return false;
+        };
+        self.redo_stack
+            .push(std::mem::replace(&mut self.round, previous));
+        true
+    }
+
+    // returns false if there is nothing to redo
+    pub fn redo(&mut self) -> bool {
+        let Some(next) = self.redo_stack.pop() else {
+            return false;
+        };
+        self.undo_stack
+            .push(std::mem::replace(&mut self.round, next));
+        true
+    }
+}
+
+fn move_item<T>(items: &mut Vec<T>, from: usize, to: usize) {
+    let item = items.remove(from);
+    items.insert(to, item);
+}
+
+// the value after current in None, values[0], values[1], .., wrapping back to None
+fn next_option<T: Copy + PartialEq>(values: &[T], current: Option<T>) -> Option<T> {
+    match current {
+        None => values.first().copied(),
+        Some(current) => values
+            .iter()
+            .position(|&value| value == current)
+            .and_then(|position| values.get(position + 1))
+            .copied(),
+    }
+}
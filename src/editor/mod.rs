@@ -0,0 +1,4 @@
+pub mod history;
+pub mod repl;
+pub mod tui;
+pub mod watch;
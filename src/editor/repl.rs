@@ -0,0 +1,180 @@
+use crate::editor::history::{Edit, RoundHistory, Row};
+use crate::scoring::round::Round;
+use crate::scoring::scorer::{calculate_score_with_trace, identify_hand};
+use crate::scoring::trace::ScoreTrace;
+use crate::validation::diagnostic::ValidationError;
+use crate::validation::round_flags::{parse_card_list, parse_joker_list};
+use std::fmt::Display;
+use std::io::{self, BufRead, Write};
+
+// File contains the interactive REPL for building a round one change at a time.
+// the round is re-scored and printed after every change
+
+const HELP: &str = "\
+Commands:
+  play <cards>          add played cards, e.g. `play AD KD:Glass`
+  hold <cards>          add cards held in hand, e.g. `hold KS:Steel`
+  joker <jokers>        add comma separated jokers, e.g. `joker Splash, Blueprint:Foil`
+  rm <play|hold|joker> <position>
+                        remove the card or joker at a 1-based position
+  show                  print the round and its score
+  undo                  undo the last change
+  redo                  redo the last undone change
+  save <file>           save the round as a YAML round file
+  help                  print this message
+  quit                  leave the REPL";
+
+// what the REPL should do after a command
+enum Outcome {
+    Changed,
+    Unchanged,
+    Quit,
+}
+
+// reads commands from input until it ends or the user quits
+pub fn run_repl(
+    mut history: RoundHistory,
+    input: impl BufRead,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    writeln!(output, "Type `help` for a list of commands")?;
+    print_round(history.round(), output)?;
+    write!(output, "> ")?;
+    output.flush()?;
+
+    for line in input.lines() {
+        let line = line?;
+
+        match run_command(&mut history, line.trim(), output)? {
+            Outcome::Changed => print_round(history.round(), output)?,
+            Outcome::Unchanged => {}
+            Outcome::Quit => return Ok(()),
+        }
+
+        write!(output, "> ")?;
+        output.flush()?;
+    }
+
+    writeln!(output)
+}
+
+fn run_command(
+    history: &mut RoundHistory,
+    line: &str,
+    output: &mut impl Write,
+) -> io::Result<Outcome> {
+    let (command, argument) = line.split_once(' ').unwrap_or((line, ""));
+    let argument = argument.trim();
+
+    let edit = match command {
+        "" => return Ok(Outcome::Unchanged),
+        "play" => parse_card_list("play", argument).map(Edit::AddPlayed),
+        "hold" => parse_card_list("hold", argument).map(Edit::AddHeld),
+        "joker" | "jokers" => parse_joker_list("joker", argument).map(Edit::AddJokers),
+        "rm" => match parse_removal(argument) {
+            Ok(edit) => Ok(edit),
+            Err(message) => return report(output, message),
+        },
+        "show" => {
+            print_round(history.round(), output)?;
+            return Ok(Outcome::Unchanged);
+        }
+        "undo" if history.undo() => return Ok(Outcome::Changed),
+        "undo" => return report(output, "Nothing to undo"),
+        "redo" if history.redo() => return Ok(Outcome::Changed),
+        "redo" => return report(output, "Nothing to redo"),
+        "save" => return save_round(history.round(), argument, output),
+        "help" => return report(output, HELP),
+        "quit" | "exit" => return Ok(Outcome::Quit),
+        _ => return report(output, format!("Unknown command `{command}`, try `help`")),
+    };
+
+    let edit = match edit {
+        Ok(edit) => edit,
+        Err(ValidationError { diagnostics }) => {
+            for diagnostic in &diagnostics {
+                writeln!(output, "{}", diagnostic.describe())?;
+            }
+            return Ok(Outcome::Unchanged);
+        }
+    };
+
+    match history.apply(edit) {
+        Ok(()) => Ok(Outcome::Changed),
+        Err(message) => report(output, message),
+    }
+}
+
+fn report(output: &mut impl Write, message: impl Display) -> io::Result<Outcome> {
+    writeln!(output, "{message}")?;
+    Ok(Outcome::Unchanged)
+}
+
+// parses the argument of `rm`, e.g. "play 2"
+fn parse_removal(argument: &str) -> Result<Edit, String> {
+    let usage = "Usage: rm <play|hold|joker> <position>";
+    let (row, position) = argument.split_once(' ').ok_or(usage)?;
+
+    let row = match row {
+        "play" => Row::Played,
+        "hold" => Row::Held,
+        "joker" | "jokers" => Row::Jokers,
+        _ => return Err(usage.to_string()),
+    };
+    let position: usize = position
+        .trim()
+        .parse()
+        .ok()
+        .filter(|&position| position > 0)
+        .ok_or_else(|| {
+            format!(
+                "Invalid position `{}`, positions start at 1",
+                position.trim()
+            )
+        })?;
+
+    Ok(Edit::Remove(row, position - 1))
+}
+
+fn save_round(round: &Round, path: &str, output: &mut impl Write) -> io::Result<Outcome> {
+    if path.is_empty() {
+        return report(output, "Usage: save <file>");
+    }
+
+    let yaml = serde_yaml::to_string(round).expect("rounds always serialize");
+    match std::fs::write(path, yaml) {
+        Ok(()) => report(output, format!("Saved to {path}")),
+        Err(err) => report(output, format!("Could not save to {path}: {err}")),
+    }
+}
+
+fn print_row<T: Display>(output: &mut impl Write, label: &str, items: &[T]) -> io::Result<()> {
+    let items: Vec<String> = items
+        .iter()
+        .enumerate()
+        .map(|(index, item)| format!("{}. {item}", index + 1))
+        .collect();
+    let row = format!("{label:<8}{}", items.join("  "));
+    writeln!(output, "{}", row.trim_end())
+}
+
+// prints the rows of the round, then the score trace and score if the round is valid
+fn print_round(round: &Round, output: &mut impl Write) -> io::Result<()> {
+    print_row(output, "Played:", &round.cards_played)?;
+    print_row(output, "Held:", &round.cards_held_in_hand)?;
+    print_row(output, "Jokers:", &round.jokers)?;
+
+    let problems = round.problems();
+    if !problems.is_empty() {
+        for problem in problems {
+            writeln!(output, "{}", problem.message)?;
+        }
+        return Ok(());
+    }
+
+    let (hand, _) = identify_hand(&round.cards_played, &round.jokers);
+    let mut trace = ScoreTrace::default();
+    let (chips, mult) = calculate_score_with_trace(round.clone(), &mut trace);
+    write!(output, "{trace}")?;
+    writeln!(output, "Score: {} ({hand})", (chips * mult).floor())
+}
@@ -0,0 +1,372 @@
+use crate::editor::history::{Edit, RoundHistory, Row};
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::scorer::{calculate_score_with_trace, identify_hand};
+use crate::scoring::trace::ScoreTrace;
+use crate::validation::diagnostic::Diagnostic;
+use crate::validation::round_flags::{parse_card_list, parse_joker_list};
+use ortalib::Suit;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+use std::io;
+use std::path::PathBuf;
+
+// File contains the full screen editor. played cards, held cards and jokers are shown..
+// as rows of tiles, with a live score and the scoring steps next to them
+
+const ROWS: [Row; 3] = [Row::Played, Row::Held, Row::Jokers];
+const CARD_TILE_WIDTH: u16 = 14;
+const JOKER_TILE_WIDTH: u16 = 20;
+const TILE_HEIGHT: u16 = 5;
+
+const HELP: &str = "←→ select  ↑↓ row  </> move  e enhancement  d edition  a add  x remove  \
+                    u undo  r redo  s save  q quit";
+
+// whether keys edit the round or type cards / jokers to add
+enum Mode {
+    Browse,
+    Add(String),
+}
+
+pub struct TuiApp {
+    history: RoundHistory,
+    row: Row,
+    // selected tile in each row, in the order of ROWS
+    selected: [usize; 3],
+    mode: Mode,
+    // message from the last action, e.g. an invalid card
+    status: String,
+    save_path: PathBuf,
+    quit: bool,
+}
+
+impl TuiApp {
+    pub fn new(history: RoundHistory, save_path: PathBuf) -> Self {
+        Self {
+            history,
+            row: Row::Played,
+            selected: [0; 3],
+            mode: Mode::Browse,
+            status: String::new(),
+            save_path,
+            quit: false,
+        }
+    }
+
+    // takes over the terminal until the user quits
+    pub fn run(mut self) -> io::Result<()> {
+        ratatui::run(|terminal| self.event_loop(terminal))
+    }
+
+    fn event_loop(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            if let Event::Key(key) = event::read()?
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key);
+            }
+        }
+        Ok(())
+    }
+
+    fn row_index(&self) -> usize {
+        ROWS.iter()
+            .position(|&row| row == self.row)
+            .expect("row is one of ROWS")
+    }
+
+    fn selected(&self) -> usize {
+        self.selected[self.row_index()]
+    }
+
+    // keeps every selection on a tile after the round changes
+    fn clamp_selection(&mut self) {
+        for (index, &row) in ROWS.iter().enumerate() {
+            let len = self.history.row_len(row);
+            self.selected[index] = self.selected[index].min(len.saturating_sub(1));
+        }
+    }
+
+    fn apply(&mut self, edit: Edit) {
+        match self.history.apply(edit) {
+            Ok(()) => self.status.clear(),
+            Err(message) => self.status = message,
+        }
+        self.clamp_selection();
+    }
+
+    fn handle_key(&mut self, key: KeyEvent) {
+        if let Mode::Add(input) = &mut self.mode {
+            match key.code {
+                KeyCode::Enter => {
+                    let input = std::mem::take(input);
+                    self.mode = Mode::Browse;
+                    self.add_entries(&input);
+                }
+                KeyCode::Esc => self.mode = Mode::Browse,
+                KeyCode::Backspace => {
+                    input.pop();
+                }
+                KeyCode::Char(character) => input.push(character),
+                _ => {}
+            }
+            return;
+        }
+
+        let row_len = self.history.row_len(self.row);
+        let selected = self.selected();
+        let shift = key.modifiers.contains(KeyModifiers::SHIFT);
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.row = ROWS[self.row_index().saturating_sub(1)];
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                self.row = ROWS[(self.row_index() + 1).min(ROWS.len() - 1)];
+            }
+            KeyCode::Left if shift => self.move_selected(-1),
+            KeyCode::Right if shift => self.move_selected(1),
+            KeyCode::Char('<') => self.move_selected(-1),
+            KeyCode::Char('>') => self.move_selected(1),
+            KeyCode::Left | KeyCode::Char('h') => {
+                self.selected[self.row_index()] = selected.saturating_sub(1);
+            }
+            KeyCode::Right | KeyCode::Char('l') => {
+                self.selected[self.row_index()] = (selected + 1).min(row_len.saturating_sub(1));
+            }
+            KeyCode::Char('e') => self.apply(Edit::CycleEnhancement(self.row, selected)),
+            KeyCode::Char('d') => self.apply(Edit::CycleEdition(self.row, selected)),
+            KeyCode::Char('x') | KeyCode::Delete => self.apply(Edit::Remove(self.row, selected)),
+            KeyCode::Char('a') => self.mode = Mode::Add(String::new()),
+            KeyCode::Char('u') => {
+                if !self.history.undo() {
+                    self.status = "Nothing to undo".to_string();
+                }
+                self.clamp_selection();
+            }
+            KeyCode::Char('r') => {
+                if !self.history.redo() {
+                    self.status = "Nothing to redo".to_string();
+                }
+                self.clamp_selection();
+            }
+            KeyCode::Char('s') => self.save(),
+            _ => {}
+        }
+    }
+
+    // moves the selected tile one place left or right, keeping it selected
+    fn move_selected(&mut self, offset: isize) {
+        let from = self.selected();
+        let Some(to) = from
+            .checked_add_signed(offset)
+            .filter(|&to| to < self.history.row_len(self.row))
+        else {
+            return;
+        };
+
+        self.apply(Edit::Move(self.row, from, to));
+        self.selected[self.row_index()] = to;
+    }
+
+    // adds cards or jokers typed in add mode to the current row
+    fn add_entries(&mut self, input: &str) {
+        let edit = match self.row {
+            Row::Played => parse_card_list("played", input).map(Edit::AddPlayed),
+            Row::Held => parse_card_list("held", input).map(Edit::AddHeld),
+            Row::Jokers => parse_joker_list("jokers", input).map(Edit::AddJokers),
+        };
+
+        match edit {
+            Ok(edit) => self.apply(edit),
+            Err(err) => {
+                self.status = err
+                    .diagnostics
+                    .iter()
+                    .map(Diagnostic::describe)
+                    .collect::<Vec<_>>()
+                    .join("; ");
+            }
+        }
+    }
+
+    fn save(&mut self) {
+        let yaml = serde_yaml::to_string(self.history.round()).expect("rounds always serialize");
+        self.status = match std::fs::write(&self.save_path, yaml) {
+            Ok(()) => format!("Saved to {}", self.save_path.display()),
+            Err(err) => format!("Could not save to {}: {err}", self.save_path.display()),
+        };
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [main_area, help_area, status_area] = Layout::vertical([
+            Constraint::Min(0),
+            Constraint::Length(1),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+        let [rows_area, score_area] =
+            Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .areas(main_area);
+        let row_areas: [Rect; 3] =
+            Layout::vertical([Constraint::Length(TILE_HEIGHT + 2); 3]).areas(rows_area);
+
+        let round = self.history.round();
+        let played_tiles: Vec<_> = round.cards_played.iter().map(card_tile).collect();
+        let held_tiles: Vec<_> = round.cards_held_in_hand.iter().map(card_tile).collect();
+        let joker_tiles: Vec<_> = round.jokers.iter().map(joker_tile).collect();
+
+        for (index, (row, tiles)) in ROWS
+            .into_iter()
+            .zip([played_tiles, held_tiles, joker_tiles])
+            .enumerate()
+        {
+            self.draw_row(frame, row_areas[index], row, tiles, self.selected[index]);
+        }
+
+        self.draw_score(frame, score_area);
+        frame.render_widget(
+            Paragraph::new(HELP).style(Style::new().fg(Color::DarkGray)),
+            help_area,
+        );
+
+        let status = match &self.mode {
+            Mode::Add(input) => format!("Add to {}: {input}_", self.row.name()),
+            Mode::Browse => self.status.clone(),
+        };
+        frame.render_widget(Paragraph::new(status), status_area);
+    }
+
+    fn draw_row(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        row: Row,
+        tiles: Vec<Vec<Line<'static>>>,
+        selected: usize,
+    ) {
+        let active = row == self.row;
+        let title = match row {
+            Row::Played => " Played ",
+            Row::Held => " Held in hand ",
+            Row::Jokers => " Jokers ",
+        };
+        let block = Block::bordered()
+            .title(title)
+            .border_style(highlight(active, Color::Cyan));
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+
+        let tile_width = if row == Row::Jokers {
+            JOKER_TILE_WIDTH
+        } else {
+            CARD_TILE_WIDTH
+        };
+        let tile_areas =
+            Layout::horizontal(vec![Constraint::Length(tile_width); tiles.len()]).split(inner);
+
+        for (index, (tile, tile_area)) in tiles.into_iter().zip(tile_areas.iter()).enumerate() {
+            let tile_block = Block::bordered()
+                .title(format!(" {} ", index + 1))
+                .border_style(highlight(active && index == selected, Color::Yellow));
+            frame.render_widget(
+                Paragraph::new(tile)
+                    .block(tile_block)
+                    .wrap(Wrap { trim: true }),
+                *tile_area,
+            );
+        }
+    }
+
+    // score and hand, followed by every scoring step, or the reasons the round is invalid
+    fn draw_score(&self, frame: &mut Frame, area: Rect) {
+        let round = self.history.round();
+        let block = Block::bordered().title(" Score ");
+        let problems = round.problems();
+
+        if !problems.is_empty() {
+            let lines: Vec<Line> = problems
+                .into_iter()
+                .map(|problem| Line::styled(problem.message, Style::new().fg(Color::Red)))
+                .collect();
+            frame.render_widget(
+                Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+                area,
+            );
+            return;
+        }
+
+        let (hand, _) = identify_hand(&round.cards_played, &round.jokers);
+        let mut trace = ScoreTrace::default();
+        let (chips, mult) = calculate_score_with_trace(round.clone(), &mut trace);
+
+        let mut lines = vec![
+            Line::styled(
+                format!("{} ({hand})", (chips * mult).floor()),
+                Style::new().add_modifier(Modifier::BOLD),
+            ),
+            Line::raw(format!("{chips} chips x {mult} mult")),
+            Line::raw(""),
+        ];
+        lines.extend(trace.steps.iter().map(|step| match step.totals {
+            Some((chips, mult)) => Line::raw(format!("{} => {chips} x {mult}", step.description)),
+            None => Line::styled(step.description.clone(), Style::new().fg(Color::DarkGray)),
+        }));
+
+        frame.render_widget(
+            Paragraph::new(lines).block(block).wrap(Wrap { trim: true }),
+            area,
+        );
+    }
+}
+
+fn highlight(selected: bool, color: Color) -> Style {
+    if selected {
+        Style::new().fg(color).add_modifier(Modifier::BOLD)
+    } else {
+        Style::new()
+    }
+}
+
+fn card_tile(card: &Card) -> Vec<Line<'static>> {
+    let suit_color = match card.suit {
+        Suit::Hearts | Suit::Diamonds => Color::Red,
+        Suit::Spades | Suit::Clubs => Color::Reset,
+    };
+    let mut lines = vec![Line::from(Span::styled(
+        format!("{}{}", card.rank, card.suit),
+        Style::new().fg(suit_color).add_modifier(Modifier::BOLD),
+    ))];
+
+    if let Some(enhancement) = card.enhancement {
+        lines.push(Line::raw(enhancement.to_string()));
+    }
+    if let Some(edition) = card.edition {
+        lines.push(Line::raw(edition.to_string()));
+    }
+    if card.bonus_chips > 0 {
+        lines.push(Line::raw(format!("+{} chips", card.bonus_chips)));
+    }
+
+    lines
+}
+
+fn joker_tile(joker_card: &JokerCard) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::raw(joker_card.joker.to_string())];
+
+    if let Some(edition) = joker_card.edition {
+        lines.push(Line::styled(
+            edition.to_string(),
+            Style::new().fg(Color::Magenta),
+        ));
+    }
+
+    lines
+}
@@ -0,0 +1,162 @@
+use crate::scoring::report::{ScoreReport, report_score};
+use crate::validation::round_file::validate_round_source;
+use std::fs;
+use std::io::{self, Write};
+use std::path::Path;
+use std::thread::sleep;
+use std::time::Duration;
+
+// File contains --watch, which re-scores a round file whenever it changes.
+// the file is polled, so no platform file watching services are needed, and each..
+// result is printed as a diff against the one before it
+
+// one line of a diff between two results
+enum DiffLine<'a> {
+    Same(&'a str),
+    Removed(&'a str),
+    Added(&'a str),
+}
+
+// the printed output of one scoring run, and its score if the round was valid
+struct WatchResult {
+    lines: Vec<String>,
+    report: Option<ScoreReport>,
+}
+
+// polls file every interval and prints a new result whenever its contents change.
+// runs until the process is stopped
+pub fn watch_round(
+    file: &Path,
+    explain: bool,
+    interval: Duration,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    let file_name = file.display().to_string();
+    let mut last_contents: Option<Result<String, String>> = None;
+    let mut previous: Option<WatchResult> = None;
+
+    loop {
+        // read errors are compared too, so a missing file is only reported once
+        let contents = fs::read_to_string(file).map_err(|err| err.to_string());
+
+        if last_contents.as_ref() != Some(&contents) {
+            let result = score_contents(&contents, &file_name, explain);
+            writeln!(output, "=== {file_name} ===")?;
+            print_result(&result, previous.as_ref(), output)?;
+            output.flush()?;
+
+            previous = Some(result);
+            last_contents = Some(contents);
+        }
+
+        sleep(interval);
+    }
+}
+
+fn score_contents(
+    contents: &Result<String, String>,
+    file_name: &str,
+    explain: bool,
+) -> WatchResult {
+    let round = match contents {
+        Ok(source) => validate_round_source(source, file_name).map_err(|err| err.to_string()),
+        Err(err) => Err(format!("Could not read `{file_name}`: {err}")),
+    };
+
+    match round {
+        Ok(round) => {
+            let report = report_score(&round, explain);
+            let mut lines: Vec<String> = report
+                .trace
+                .iter()
+                .flatten()
+                .map(|step| match step.totals {
+                    Some((chips, mult)) => format!("{} => {chips} x {mult}", step.description),
+                    None => step.description.clone(),
+                })
+                .collect();
+            lines.push(format!("{} ({})", report.score, report.hand));
+
+            WatchResult {
+                lines,
+                report: Some(report),
+            }
+        }
+        Err(message) => WatchResult {
+            lines: message
+                .lines()
+                .map(|line| format!("Error: {line}"))
+                .collect(),
+            report: None,
+        },
+    }
+}
+
+// prints result, marking the lines that changed since previous
+fn print_result(
+    result: &WatchResult,
+    previous: Option<&WatchResult>,
+    output: &mut impl Write,
+) -> io::Result<()> {
+    let Some(previous) = previous else {
+        for line in &result.lines {
+            writeln!(output, "{line}")?;
+        }
+        return Ok(());
+    };
+
+    for diff_line in diff_lines(&previous.lines, &result.lines) {
+        match diff_line {
+            DiffLine::Same(line) => writeln!(output, "  {line}")?,
+            DiffLine::Removed(line) => writeln!(output, "- {line}")?,
+            DiffLine::Added(line) => writeln!(output, "+ {line}")?,
+        }
+    }
+
+    if let (Some(before), Some(after)) = (&previous.report, &result.report) {
+        writeln!(
+            output,
+            "Score: {} -> {} ({:+})",
+            before.score,
+            after.score,
+            after.score - before.score
+        )?;
+    }
+
+    Ok(())
+}
+
+// line diff from the longest common subsequence of before and after
+fn diff_lines<'a>(before: &'a [String], after: &'a [String]) -> Vec<DiffLine<'a>> {
+    // common[i][j] is the length of the longest common subsequence of before[i..] and after[j..]
+    let mut common = vec![vec![0; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            common[i][j] = if before[i] == after[j] {
+                common[i + 1][j + 1] + 1
+            } else {
+                common[i + 1][j].max(common[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = vec![];
+    let (mut i, mut j) = (0, 0);
+    while i < before.len() && j < after.len() {
+        if before[i] == after[j] {
+            diff.push(DiffLine::Same(&before[i]));
+            i += 1;
+            j += 1;
+        } else if common[i + 1][j] >= common[i][j + 1] {
+            diff.push(DiffLine::Removed(&before[i]));
+            i += 1;
+        } else {
+            diff.push(DiffLine::Added(&after[j]));
+            j += 1;
+        }
+    }
+    diff.extend(before[i..].iter().map(|line| DiffLine::Removed(line)));
+    diff.extend(after[j..].iter().map(|line| DiffLine::Added(line)));
+
+    diff
+}
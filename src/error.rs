@@ -0,0 +1,96 @@
+use crate::validation::diagnostic::{Diagnostic, ValidationError};
+use serde::Serialize;
+use std::fmt::{Debug, Display, Formatter};
+use std::io;
+
+// File contains the calculator's error type and the exit code for each kind of failure.
+//
+// exit codes:
+//   0  success
+//   2  invalid command line usage (reported by clap)
+//   3  the round could not be read
+//   4  the round file is malformed
+//   5  the round is well formed but describes an impossible game state
+//   6  an option is invalid for the round, e.g. tarot targets out of range
+
+pub enum OrtalabError {
+    // reading the round file or stdin failed
+    Io { path: String, source: io::Error },
+    // the round file is not valid YAML or has invalid cards, jokers or fields
+    Parse(ValidationError),
+    // the round can't exist in game, e.g. too many cards played
+    InvalidRound(ValidationError),
+    // an option doesn't fit the round
+    InvalidArgument(String),
+}
+
+// error as written by --output json
+#[derive(Serialize)]
+struct JsonError<'a> {
+    kind: &'static str,
+    exit_code: u8,
+    message: String,
+    diagnostics: &'a [Diagnostic],
+}
+
+impl OrtalabError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            OrtalabError::Io { .. } => "io",
+            OrtalabError::Parse(_) => "parse",
+            OrtalabError::InvalidRound(_) => "invalid_round",
+            OrtalabError::InvalidArgument(_) => "invalid_argument",
+        }
+    }
+
+    pub fn exit_code(&self) -> u8 {
+        match self {
+            OrtalabError::Io { .. } => 3,
+            OrtalabError::Parse(_) => 4,
+            OrtalabError::InvalidRound(_) => 5,
+            OrtalabError::InvalidArgument(_) => 6,
+        }
+    }
+
+    // diagnostics found in the round file, if any
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            OrtalabError::Parse(validation_error)
+            | OrtalabError::InvalidRound(validation_error) => &validation_error.diagnostics,
+            _ => &[],
+        }
+    }
+
+    // error object written by --output json
+    pub fn to_json(&self) -> String {
+        let json_error = JsonError {
+            kind: self.kind(),
+            exit_code: self.exit_code(),
+            message: self.to_string(),
+            diagnostics: self.diagnostics(),
+        };
+
+        serde_json::json!({ "error": json_error }).to_string()
+    }
+}
+
+impl Display for OrtalabError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            OrtalabError::Io { path, source } => write!(f, "Could not read `{path}`: {source}"),
+            OrtalabError::Parse(validation_error)
+            | OrtalabError::InvalidRound(validation_error) => {
+                write!(f, "{validation_error}")
+            }
+            OrtalabError::InvalidArgument(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl Debug for OrtalabError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for OrtalabError {}
@@ -0,0 +1,69 @@
+use crate::error::OrtalabError;
+use crate::scoring::report::report_score;
+use crate::validation::round_file::validate_round_source;
+use std::ffi::{CStr, CString, c_char};
+use std::panic::catch_unwind;
+
+// File contains the C API of the shared library, declared in include/ortalab.h.
+// rounds go in as YAML or JSON strings and results come back as JSON strings, using..
+// the same objects as --output json --explain. every returned string must be..
+// released with ortalab_free_string
+
+// file name used in diagnostics for rounds passed through the C API
+const FFI_SOURCE_NAME: &str = "<round>";
+
+fn error_json(kind: &str, message: &str) -> String {
+    serde_json::json!({ "error": { "kind": kind, "message": message } }).to_string()
+}
+
+fn score_source(source: &str) -> String {
+    match validate_round_source(source, FFI_SOURCE_NAME) {
+        Ok(round) => serde_json::to_string(&report_score(&round, true))
+            .expect("score reports always serialize"),
+        Err(err @ (OrtalabError::Parse(_) | OrtalabError::InvalidRound(_))) => err.to_json(),
+        Err(err) => error_json(err.kind(), &err.to_string()),
+    }
+}
+
+fn into_c_string(json: String) -> *mut c_char {
+    // serde_json escapes control characters, so the JSON never contains a nul byte
+    CString::new(json)
+        .expect("JSON has no interior nul bytes")
+        .into_raw()
+}
+
+/// Scores a YAML or JSON round and returns the result as a JSON string.
+///
+/// # Safety
+///
+/// `round` must be null or point to a nul-terminated string that stays valid for the
+/// duration of the call. The returned string must be freed with `ortalab_free_string`.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ortalab_score(round: *const c_char) -> *mut c_char {
+    if round.is_null() {
+        return into_c_string(error_json("invalid_argument", "round is null"));
+    }
+
+    // SAFETY: the caller guarantees round is a valid nul-terminated string
+    let round = unsafe { CStr::from_ptr(round) };
+    let json = match round.to_str() {
+        Ok(source) => catch_unwind(|| score_source(source))
+            .unwrap_or_else(|_| error_json("internal", "the scorer panicked")),
+        Err(_) => error_json("invalid_argument", "round is not valid UTF-8"),
+    };
+
+    into_c_string(json)
+}
+
+/// Frees a string returned by `ortalab_score`. Passing null does nothing.
+///
+/// # Safety
+///
+/// `result` must be null or a string returned by `ortalab_score` that hasn't been freed.
+#[unsafe(no_mangle)]
+pub unsafe extern "C" fn ortalab_free_string(result: *mut c_char) {
+    if !result.is_null() {
+        // SAFETY: the caller guarantees result came from CString::into_raw in ortalab_score
+        drop(unsafe { CString::from_raw(result) });
+    }
+}
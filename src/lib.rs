@@ -0,0 +1,9 @@
+pub mod editor;
+pub mod error;
+pub mod ffi;
+pub mod scoring;
+pub mod search;
+pub mod service;
+pub mod simulate;
+pub mod tarot;
+pub mod validation;
@@ -1,43 +1,619 @@
 use std::{
-    error::Error,
     fs::File,
-    io::{Read, stdin},
+    io::{Read, stdin, stdout},
     path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
 };
 
-use clap::Parser;
-use ortalib::{Chips, Mult, Round};
-mod scoring;
-use scoring::scorer;
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use ortalab::editor::history::RoundHistory;
+use ortalab::editor::tui::TuiApp;
+use ortalab::error::OrtalabError;
+use ortalab::scoring::card::playing_card::Card;
+use ortalab::scoring::joker::joker_card::JokerCard;
+use ortalab::scoring::report;
+use ortalab::scoring::round::Round;
+use ortalab::scoring::scorer;
+use ortalab::scoring::trace::ScoreTrace;
+use ortalab::search::discard::DiscardSearch;
+use ortalab::search::parallel::SearchOptions;
+use ortalab::simulate;
+use ortalab::simulate::odds::DrawOdds;
+use ortalab::simulate::simulation::{PlayPolicy, Simulation};
+use ortalab::tarot::target_search;
+use ortalab::tarot::tarot_card::Tarot;
+use ortalab::tarot::what_if;
+use ortalab::{editor, service, validation};
+use ortalib::{Chips, Mult};
 
 #[derive(Parser)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 struct Opts {
-    file: PathBuf,
+    #[command(subcommand)]
+    command: Option<Command>,
 
+    #[command(flatten)]
+    round: RoundArgs,
+
+    /// Print every scoring step before the score
     #[arg(long)]
     explain: bool,
+
+    /// Re-score the round file whenever it changes, showing what changed since the
+    /// last result
+    #[arg(long, requires = "file")]
+    watch: bool,
+
+    /// How often --watch checks the round file, in milliseconds
+    #[arg(long, default_value_t = 500, requires = "watch")]
+    interval: u64,
+
+    /// Read one JSON or flow-YAML round per line from stdin and print one result per line
+    #[arg(long, exclusive = true)]
+    stream: bool,
+
+    /// Format of the score and of errors. Errors exit with a distinct code for each
+    /// kind of failure, see the README
+    #[arg(long, value_enum, global = true, default_value_t = OutputFormat::Text)]
+    output: OutputFormat,
+}
+
+#[derive(Clone, Copy, PartialEq, ValueEnum)]
+enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Preview the score change from using a tarot on some cards
+    WhatIf(WhatIfOpts),
+    /// Find the cards to use held tarots on that give the best playable score
+    TarotSearch(TarotSearchOpts),
+    /// Print a round with every card and joker in its canonical form
+    #[command(alias = "normalize")]
+    Normalise(RoundArgs),
+    /// Build a round one change at a time, re-scoring after every change
+    Repl(ReplOpts),
+    /// Edit a round in a full screen editor with a live score
+    Tui(TuiOpts),
+    /// Serve scoring over HTTP on localhost
+    Serve(ServeOpts),
+    /// Draw random hands from a deck and report how the jokers score on average
+    Simulate(SimulateOpts),
+    /// Rank the cards to discard by the expected best score after redrawing
+    Discard(DiscardOpts),
+    /// Exact chance of making each poker hand after drawing cards
+    Odds(OddsOpts),
+}
+
+// where to read the round from: a round file, or the cards and jokers as flags
+#[derive(Args)]
+struct RoundArgs {
+    /// Round file, or `-` to read from stdin
+    #[arg(required_unless_present = "play", conflicts_with_all = ["play", "hold", "jokers"])]
+    file: Option<PathBuf>,
+
+    /// Played cards instead of a round file, e.g. "AD KD QD". Attach modifiers with
+    /// `:`, e.g. "KS:Steel:Foil". Separate with commas to use long names, e.g.
+    /// "Ace of Diamonds, King of Diamonds:Glass"
+    #[arg(long)]
+    play: Option<String>,
+
+    /// Cards held in hand, written the same way as --play
+    #[arg(long, requires = "play")]
+    hold: Option<String>,
+
+    /// Comma separated jokers, e.g. "Splash,Blueprint:Foil"
+    #[arg(long, requires = "play")]
+    jokers: Option<String>,
+}
+
+#[derive(Args)]
+struct WhatIfOpts {
+    #[command(flatten)]
+    round: RoundArgs,
+
+    /// Tarot to use, e.g. "The Empress"
+    #[arg(long)]
+    tarot: Tarot,
+
+    /// Comma separated 1-based positions of the target cards. Played cards are
+    /// counted first, then cards held in hand
+    #[arg(long)]
+    targets: String,
+
+    /// Print every scoring step of the updated round
+    #[arg(long)]
+    explain: bool,
+}
+
+#[derive(Args)]
+struct TarotSearchOpts {
+    #[command(flatten)]
+    round: RoundArgs,
+
+    /// Held tarot, e.g. "Strength". Repeat to use several tarots, in order
+    #[arg(long = "tarot", required = true)]
+    tarots: Vec<Tarot>,
+
+    /// Number of worker threads to search with, defaults to one per core
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+}
+
+#[derive(Args)]
+struct ReplOpts {
+    /// Round file to start from, otherwise the REPL starts with an empty round
+    file: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct TuiOpts {
+    /// Round file to edit, otherwise the editor starts with an empty round
+    file: Option<PathBuf>,
+
+    /// Where `s` saves the round. Defaults to the round file, or round.yml
+    #[arg(long)]
+    save: Option<PathBuf>,
+}
+
+#[derive(Args)]
+struct SimulateOpts {
+    /// Deck to draw from: standard, abandoned, checkered, or the deck's cards
+    /// written the same way as --play
+    #[arg(long, default_value = "standard")]
+    deck: String,
+
+    /// File listing the deck's cards, written the same way as --play
+    #[arg(long, conflicts_with = "deck")]
+    deck_file: Option<PathBuf>,
+
+    /// Number of cards drawn into each hand
+    #[arg(long, default_value_t = 8)]
+    hand_size: usize,
+
+    /// Comma separated jokers, e.g. "Splash,Blueprint:Foil"
+    #[arg(long)]
+    jokers: Option<String>,
+
+    /// How the cards to play are picked: best-score tries every play and keeps the
+    /// highest score, best-hand plays the cards making the best poker hand
+    #[arg(long, default_value_t = PlayPolicy::BestScore)]
+    policy: PlayPolicy,
+
+    /// Number of hands to draw
+    #[arg(long, default_value_t = 1000)]
+    hands: usize,
+
+    /// Seed for the random draws. The same seed always gives the same report
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Number of worker threads to simulate with, defaults to one per core
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
+#[derive(Args)]
+struct DiscardOpts {
+    /// Cards in hand, written the same way as --play
+    #[arg(long)]
+    hand: String,
+
+    /// Cards left to draw: standard, abandoned or checkered without the cards in
+    /// hand, or the remaining cards written the same way as --play
+    #[arg(long, default_value = "standard")]
+    deck: String,
+
+    /// File listing the cards left to draw, written the same way as --play
+    #[arg(long, conflicts_with = "deck")]
+    deck_file: Option<PathBuf>,
+
+    /// Comma separated jokers, e.g. "Splash,Blueprint:Foil"
+    #[arg(long)]
+    jokers: Option<String>,
+
+    /// Number of discards left this round
+    #[arg(long, default_value_t = 3)]
+    discards: usize,
+
+    /// Score to reach, adds the chance of reaching it after each discard
+    #[arg(long)]
+    target: Option<f64>,
+
+    /// Number of discard options to show, best first
+    #[arg(long, default_value_t = 10)]
+    top: usize,
+
+    /// Most draws to score exactly for one discard, more are sampled
+    #[arg(long, default_value_t = 1000)]
+    exact_limit: usize,
+
+    /// Number of draws sampled for a discard with too many to score exactly
+    #[arg(long, default_value_t = 200)]
+    samples: usize,
+
+    /// Seed for sampled draws. The same seed always gives the same advice
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// Number of worker threads to search with, defaults to one per core
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+}
+
+#[derive(Args)]
+struct OddsOpts {
+    /// Cards held in hand, written the same way as --play
+    #[arg(long)]
+    hold: Option<String>,
+
+    /// Number of cards drawn
+    #[arg(long)]
+    draws: usize,
+
+    /// Cards left to draw: standard, abandoned or checkered without the held cards,
+    /// or the remaining cards written the same way as --play
+    #[arg(long, default_value = "standard")]
+    deck: String,
+
+    /// File listing the cards left to draw, written the same way as --play
+    #[arg(long, conflicts_with = "deck")]
+    deck_file: Option<PathBuf>,
+
+    /// Comma separated jokers. Four Fingers, Shortcut and Smeared Joker change the
+    /// hands that can be made
+    #[arg(long)]
+    jokers: Option<String>,
+
+    /// Number of worker threads to count with, defaults to one per core
+    #[arg(long, value_parser = clap::value_parser!(u32).range(1..))]
+    jobs: Option<u32>,
+}
+
+#[derive(Args)]
+struct ServeOpts {
+    /// Port to listen on. Only localhost clients can connect
+    #[arg(long, default_value_t = 8080)]
+    port: u16,
+}
+
+fn main() -> ExitCode {
     let opts = Opts::parse();
-    let round = parse_round(&opts)?;
+    let output = opts.output;
 
-    let (chips, mult) = score(round);
+    match run(opts) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            match output {
+                OutputFormat::Text => eprintln!("Error: {err}"),
+                OutputFormat::Json => println!("{}", err.to_json()),
+            }
+            ExitCode::from(err.exit_code())
+        }
+    }
+}
 
-    println!("{}", (chips * mult).floor());
+fn run(opts: Opts) -> Result<(), OrtalabError> {
+    match opts.command {
+        Some(Command::WhatIf(what_if_opts)) => run_what_if(what_if_opts),
+        Some(Command::TarotSearch(search_opts)) => run_tarot_search(search_opts),
+        Some(Command::Normalise(round_args)) => run_normalise(round_args, opts.output),
+        Some(Command::Repl(repl_opts)) => run_repl(repl_opts),
+        Some(Command::Tui(tui_opts)) => run_tui(tui_opts),
+        Some(Command::Simulate(simulate_opts)) => run_simulate(simulate_opts, opts.output),
+        Some(Command::Discard(discard_opts)) => run_discard(discard_opts, opts.output),
+        Some(Command::Odds(odds_opts)) => run_odds(odds_opts, opts.output),
+        Some(Command::Serve(serve_opts)) => {
+            service::routes::serve(serve_opts.port).map_err(|source| OrtalabError::Io {
+                path: format!("localhost:{}", serve_opts.port),
+                source,
+            })
+        }
+        None if opts.stream => service::stream::run_stream(stdin().lock(), &mut stdout().lock())
+            .map_err(|source| OrtalabError::Io {
+                path: "<stdin>".to_string(),
+                source,
+            }),
+        None if opts.watch => {
+            let file = opts.round.file.expect("clap requires a file with --watch");
+            editor::watch::watch_round(
+                &file,
+                opts.explain,
+                Duration::from_millis(opts.interval),
+                &mut stdout().lock(),
+            )
+            .map_err(|source| OrtalabError::Io {
+                path: file.display().to_string(),
+                source,
+            })
+        }
+        None => {
+            let round = parse_round(&opts.round)?;
+            print_score(round, opts.explain, opts.output);
+            Ok(())
+        }
+    }
+}
+
+fn run_what_if(opts: WhatIfOpts) -> Result<(), OrtalabError> {
+    let round = parse_round(&opts.round)?;
+    let hand_size = round.cards_played.len() + round.cards_held_in_hand.len();
+    let targets =
+        what_if::parse_targets(&opts.targets, hand_size).map_err(OrtalabError::InvalidArgument)?;
+
+    // a tarot only fails to apply when --targets doesn't fit it, e.g. one card for Death
+    let preview = what_if::preview_tarot(&round, opts.tarot, &targets)
+        .map_err(OrtalabError::InvalidArgument)?;
+    println!("{preview}");
+
+    if opts.explain {
+        let updated_round = what_if::apply_tarot(&round, opts.tarot, &targets)
+            .map_err(OrtalabError::InvalidArgument)?;
+        let mut trace = ScoreTrace::default();
+        scorer::calculate_score_with_trace(updated_round, &mut trace);
+        print!("{trace}");
+    }
     Ok(())
 }
 
-fn parse_round(opts: &Opts) -> Result<Round, Box<dyn Error>> {
+fn run_tarot_search(opts: TarotSearchOpts) -> Result<(), OrtalabError> {
+    let round = parse_round(&opts.round)?;
+
+    // played and held cards together make up the hand the tarots are used on
+    let hand: Vec<_> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .copied()
+        .collect();
+
+    match target_search::search_tarot_targets(
+        &hand,
+        &round.jokers,
+        &opts.tarots,
+        &search_options(opts.jobs),
+    )? {
+        Some(result) => println!("{result}"),
+        None => println!("No legal targets for the given tarots"),
+    }
+    Ok(())
+}
+
+// reads a deck from --deck-file if given, otherwise from --deck
+fn parse_deck_args(deck: &str, deck_file: Option<&Path>) -> Result<Vec<Card>, OrtalabError> {
+    let Some(file) = deck_file else {
+        return simulate::deck::parse_deck("--deck", deck);
+    };
+
+    let cards = std::fs::read_to_string(file).map_err(|source| OrtalabError::Io {
+        path: file.display().to_string(),
+        source,
+    })?;
+    simulate::deck::parse_deck("--deck-file", &cards)
+}
+
+fn parse_jokers_arg(jokers: Option<&str>) -> Result<Vec<JokerCard>, OrtalabError> {
+    jokers.map_or(Ok(vec![]), |jokers| {
+        validation::round_flags::parse_joker_list("--jokers", jokers).map_err(OrtalabError::Parse)
+    })
+}
+
+fn run_simulate(opts: SimulateOpts, output: OutputFormat) -> Result<(), OrtalabError> {
+    let deck = parse_deck_args(&opts.deck, opts.deck_file.as_deref())?;
+    let jokers = parse_jokers_arg(opts.jokers.as_deref())?;
+
+    let simulation = Simulation {
+        deck,
+        hand_size: opts.hand_size,
+        jokers,
+        policy: opts.policy,
+        hands: opts.hands,
+        seed: opts.seed,
+    };
+    simulation.validate()?;
+    let report = simulation.run(&search_options(opts.jobs));
+
+    match output {
+        OutputFormat::Text => println!("{report}"),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string(&report).expect("simulation reports always serialize")
+        ),
+    }
+    Ok(())
+}
+
+// reads the cards left to draw. a preset is the full deck, so the cards in hand are..
+// taken out of it
+fn parse_remaining_deck(
+    deck: &str,
+    deck_file: Option<&Path>,
+    hand: &[Card],
+) -> Result<Vec<Card>, OrtalabError> {
+    let cards = parse_deck_args(deck, deck_file)?;
+    if deck_file.is_none() && simulate::deck::preset_deck(deck).is_some() {
+        return Ok(simulate::deck::remaining_deck(&cards, hand));
+    }
+    Ok(cards)
+}
+
+fn run_discard(opts: DiscardOpts, output: OutputFormat) -> Result<(), OrtalabError> {
+    let hand = validation::round_flags::parse_card_list("--hand", &opts.hand)
+        .map_err(OrtalabError::Parse)?;
+
+    let search = DiscardSearch {
+        deck: parse_remaining_deck(&opts.deck, opts.deck_file.as_deref(), &hand)?,
+        hand,
+        jokers: parse_jokers_arg(opts.jokers.as_deref())?,
+        discards_left: opts.discards,
+        target: opts.target,
+        exact_limit: opts.exact_limit,
+        samples: opts.samples,
+        seed: opts.seed,
+    };
+    search.validate()?;
+    let mut advice = search.run(&search_options(opts.jobs));
+    advice.truncate(opts.top);
+
+    match output {
+        OutputFormat::Text => println!("{advice}"),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string(&advice).expect("discard advice always serializes")
+        ),
+    }
+    Ok(())
+}
+
+fn run_odds(opts: OddsOpts, output: OutputFormat) -> Result<(), OrtalabError> {
+    let held =
+        validation::round_flags::parse_card_list("--hold", opts.hold.as_deref().unwrap_or(""))
+            .map_err(OrtalabError::Parse)?;
+
+    let odds = DrawOdds {
+        deck: parse_remaining_deck(&opts.deck, opts.deck_file.as_deref(), &held)?,
+        held,
+        jokers: parse_jokers_arg(opts.jokers.as_deref())?,
+        draws: opts.draws,
+    };
+    odds.validate()?;
+    let report = odds.run(&search_options(opts.jobs));
+
+    match output {
+        OutputFormat::Text => println!("{report}"),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string(&report).expect("odds reports always serialize")
+        ),
+    }
+    Ok(())
+}
+
+// worker threads for a search, one per core unless --jobs is given
+fn search_options(jobs: Option<u32>) -> SearchOptions {
+    jobs.map_or_else(SearchOptions::default, |jobs| {
+        SearchOptions::with_workers(jobs as usize)
+    })
+}
+
+fn run_normalise(round_args: RoundArgs, output: OutputFormat) -> Result<(), OrtalabError> {
+    let round = parse_round(&round_args)?;
+
+    match output {
+        OutputFormat::Text => print!(
+            "{}",
+            serde_yaml::to_string(&round).expect("rounds always serialize")
+        ),
+        OutputFormat::Json => println!(
+            "{}",
+            serde_json::to_string(&round).expect("rounds always serialize")
+        ),
+    }
+    Ok(())
+}
+
+// reads the round to start an editor with, or an empty round without a file
+fn parse_starting_round(file: Option<&Path>) -> Result<Round, OrtalabError> {
+    match file {
+        Some(file) => parse_round(&RoundArgs {
+            file: Some(file.to_path_buf()),
+            play: None,
+            hold: None,
+            jokers: None,
+        }),
+        None => Ok(Round::default()),
+    }
+}
+
+fn run_repl(opts: ReplOpts) -> Result<(), OrtalabError> {
+    let round = parse_starting_round(opts.file.as_deref())?;
+
+    editor::repl::run_repl(
+        RoundHistory::new(round),
+        stdin().lock(),
+        &mut stdout().lock(),
+    )
+    .map_err(|source| OrtalabError::Io {
+        path: "<stdin>".to_string(),
+        source,
+    })
+}
+
+fn run_tui(opts: TuiOpts) -> Result<(), OrtalabError> {
+    let round = parse_starting_round(opts.file.as_deref())?;
+    let save_path = opts
+        .save
+        .or(opts.file.filter(|file| file.as_path() != Path::new("-")))
+        .unwrap_or_else(|| PathBuf::from("round.yml"));
+
+    TuiApp::new(RoundHistory::new(round), save_path)
+        .run()
+        .map_err(|source| OrtalabError::Io {
+            path: "<terminal>".to_string(),
+            source,
+        })
+}
+
+// prints the score of round, preceded by the score trace if explain is set.
+// json output is a single object with the score, hand, chips and mult, and the trace..
+// if explain is set
+fn print_score(round: Round, explain: bool, output: OutputFormat) {
+    if output == OutputFormat::Json {
+        let report = report::report_score(&round, explain);
+        println!(
+            "{}",
+            serde_json::to_string(&report).expect("score reports always serialize")
+        );
+        return;
+    }
+
+    let (chips, mult) = if explain {
+        let mut trace = ScoreTrace::default();
+        let score = scorer::calculate_score_with_trace(round, &mut trace);
+        print!("{trace}");
+        score
+    } else {
+        score(round)
+    };
+
+    println!("{}", (chips * mult).floor());
+}
+
+// reads the round from the round flags if given, otherwise from the round file
+fn parse_round(round_args: &RoundArgs) -> Result<Round, OrtalabError> {
+    let Some(file) = &round_args.file else {
+        let play = round_args
+            .play
+            .as_deref()
+            .expect("clap requires --play without a round file");
+        return validation::round_flags::validate_round_flags(
+            play,
+            round_args.hold.as_deref(),
+            round_args.jokers.as_deref(),
+        );
+    };
+
+    let file_name = if file.as_path() == Path::new("-") {
+        "<stdin>".to_string()
+    } else {
+        file.display().to_string()
+    };
+
     let mut input = String::new();
-    if opts.file == Path::new("-") {
-        stdin().read_to_string(&mut input)?;
+    let read_result = if file.as_path() == Path::new("-") {
+        stdin().read_to_string(&mut input)
     } else {
-        File::open(&opts.file)?.read_to_string(&mut input)?;
-    }
+        File::open(file).and_then(|mut file| file.read_to_string(&mut input))
+    };
+    read_result.map_err(|source| OrtalabError::Io {
+        path: file_name.clone(),
+        source,
+    })?;
 
-    let round = serde_yaml::from_str(&input)?;
-    Ok(round)
+    validation::round_file::validate_round_source(&input, &file_name)
 }
 
 fn score(round: Round) -> (Chips, Mult) {
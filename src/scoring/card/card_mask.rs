@@ -0,0 +1,123 @@
+use crate::scoring::card::card_util::rank_to_order;
+use crate::scoring::card::playing_card::{Card, Enhancement};
+use crate::scoring::joker::joker_wrappers::JokerEffectFlags;
+use ortalib::{Rank, Suit};
+use std::ops::Deref;
+
+// File contains the compact card representation used by hand detection and scoring.
+// ranks and suits are packed into bitsets and card lists into fixed-size arrays, so..
+// evaluating a hand doesn't allocate
+
+// most cards that can make a poker hand
+pub const MAX_HAND_SIZE: usize = 5;
+
+// most cards a CardMask can hold, one bit each
+pub const MAX_MASK_CARDS: usize = 16;
+
+// suits in bit order for suit masks
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+
+// a mask with every suit set, what a Wild card counts as
+pub const ALL_SUITS: u8 = 0b1111;
+
+// set of positions in a list of cards, one bit per position
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub struct CardMask(pub u16);
+
+impl CardMask {
+    pub fn contains(&self, position: usize) -> bool {
+        position < MAX_MASK_CARDS && self.0 & (1 << position) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    // positions in the mask, lowest first
+    pub fn positions(self) -> impl Iterator<Item = usize> {
+        let mut bits = self.0;
+        std::iter::from_fn(move || {
+            if bits == 0 {
+                return None;
+            }
+            let position = bits.trailing_zeros() as usize;
+            bits &= bits - 1;
+            Some(position)
+        })
+    }
+}
+
+// bit for a single suit in a suit mask
+pub fn suit_bit(suit: Suit) -> u8 {
+    let index = SUITS.iter().position(|&other| other == suit).unwrap_or(0);
+    1 << index
+}
+
+// suits card counts as for flushes. Wild cards count as every suit, Stone cards as..
+// none, and with Smeared Joker a card counts as both suits of its color
+pub fn suit_mask(card: &Card, joker_effects: &JokerEffectFlags) -> u8 {
+    if card.is_stone() {
+        return 0;
+    }
+    if card.enhancement == Some(Enhancement::Wild) {
+        return ALL_SUITS;
+    }
+    if joker_effects.smeared_joker {
+        return SUITS
+            .iter()
+            .filter(|suit| suit.color() == card.suit.color())
+            .fold(0, |mask, &suit| mask | suit_bit(suit));
+    }
+    suit_bit(card.suit)
+}
+
+// index of rank from 0 (Two) to 12 (Ace), its bit in a rank set
+pub fn rank_index(rank: &Rank) -> u8 {
+    (rank_to_order(rank, false) - 2) as u8
+}
+
+// up to five cards kept in place, e.g. the cards that make a hand
+#[derive(Clone, Copy)]
+pub struct CardList {
+    // only the first len cards are part of the list
+    cards: [Card; MAX_HAND_SIZE],
+    len: usize,
+}
+
+impl CardList {
+    // the cards of cards at the positions in mask, in order. positions past the first..
+    // five are left out
+    pub fn select(cards: &[Card], mask: CardMask) -> Self {
+        let filler = cards
+            .first()
+            .copied()
+            .unwrap_or_else(|| Card::new(Rank::Two, Suit::Spades, None, None));
+        let mut list = [filler; MAX_HAND_SIZE];
+        let mut len = 0;
+
+        for position in mask.positions() {
+            let Some(&card) = cards.get(position) else {
+                break;
+            };
+            if len == MAX_HAND_SIZE {
+                break;
+            }
+            list[len] = card;
+            len += 1;
+        }
+
+        CardList { cards: list, len }
+    }
+}
+
+impl Deref for CardList {
+    type Target = [Card];
+
+    fn deref(&self) -> &[Card] {
+        &self.cards[..self.len]
+    }
+}
@@ -0,0 +1,326 @@
+use crate::scoring::card::card_mask::{
+    CardList, CardMask, MAX_HAND_SIZE, MAX_MASK_CARDS, rank_index, suit_mask,
+};
+use crate::scoring::card::playing_card::{Card, Edition, Enhancement};
+use crate::scoring::joker::joker_wrappers::JokerEffectFlags;
+use ortalib::PokerHand;
+
+// File contains the subset based hand evaluator used to find the best hand and every..
+// hand the played cards contain.
+// every subset of up to five played cards is classified by the hands its cards make..
+// on their own, so a hand that needs two patterns (e.g. straight flush) is only..
+// found when the same cards make both. the best subset is the highest hand, then..
+// the most cards, then the most chips from ranks, then the highest ranks compared from..
+// the highest card down, then what the cards' enhancements, editions and bonus chips..
+// add to the score, then the earliest in played order. so the result never depends..
+// on anything but the cards and their order.
+// the played cards contain a hand if any subset makes it, so a full house contains..
+// two pair but four of a kind doesn't. Stone cards are never part of a hand, but..
+// always score alongside it.
+// cards are packed into rank and suit bitsets first, so evaluation doesn't allocate
+
+// every poker hand, lowest first. also the bit order for hand sets
+pub const HANDS: [PokerHand; 12] = [
+    PokerHand::HighCard,
+    PokerHand::Pair,
+    PokerHand::TwoPair,
+    PokerHand::ThreeOfAKind,
+    PokerHand::Straight,
+    PokerHand::Flush,
+    PokerHand::FullHouse,
+    PokerHand::FourOfAKind,
+    PokerHand::StraightFlush,
+    PokerHand::FiveOfAKind,
+    PokerHand::FlushHouse,
+    PokerHand::FlushFive,
+];
+
+// bit for hand in a hand set
+fn hand_bit(hand: PokerHand) -> u16 {
+    1 << hand as u16
+}
+
+// rank and suits of a played card, see card_mask.rs, and what it adds to the score..
+// when it scores besides its rank
+#[derive(Clone, Copy, Default)]
+struct PackedCard {
+    rank: u8,
+    suits: u8,
+    // from Bonus, Foil and permanent bonus chips
+    extra_chips: u32,
+    // from Mult and Holographic
+    added_mult: u8,
+    // twice the mult the card multiplies by, 2 for none, 4 for Glass, 3 for Polychrome..
+    // and 6 for both
+    mult_factor: u8,
+}
+
+impl PackedCard {
+    fn new(card: &Card, joker_effects: &JokerEffectFlags) -> Self {
+        let enhancement = |enhancement| card.enhancement == Some(enhancement);
+        let edition = |edition| card.edition == Some(edition);
+
+        let mut extra_chips = card.bonus_chips;
+        let mut added_mult = 0;
+        let mut mult_factor = 2;
+        if enhancement(Enhancement::Bonus) {
+            extra_chips = extra_chips.saturating_add(30);
+        }
+        if edition(Edition::Foil) {
+            extra_chips = extra_chips.saturating_add(50);
+        }
+        if enhancement(Enhancement::Mult) {
+            added_mult += 4;
+        }
+        if edition(Edition::Holographic) {
+            added_mult += 10;
+        }
+        if enhancement(Enhancement::Glass) {
+            mult_factor *= 2;
+        }
+        if edition(Edition::Polychrome) {
+            mult_factor = mult_factor * 3 / 2;
+        }
+
+        PackedCard {
+            rank: rank_index(&card.rank),
+            suits: suit_mask(card, joker_effects),
+            extra_chips,
+            added_mult,
+            mult_factor,
+        }
+    }
+}
+
+// number of cards a flush or straight needs, four with Four Fingers
+fn pattern_size(joker_effects: &JokerEffectFlags) -> usize {
+    if joker_effects.four_fingers { 4 } else { 5 }
+}
+
+// checks whether the ranks in rank_bits run without gaps wider than max_step.
+// bit 0 is a low Ace, bits 1 to 13 are Two to Ace
+fn is_run(rank_bits: u16, max_step: u32) -> bool {
+    let mut bits = rank_bits;
+    let mut previous: Option<u32> = None;
+
+    while bits != 0 {
+        let bit = bits.trailing_zeros();
+        if previous.is_some_and(|previous| bit - previous > max_step) {
+            return false;
+        }
+        previous = Some(bit);
+        bits &= bits - 1;
+    }
+
+    true
+}
+
+// checks whether a set of distinct ranks makes a straight with Ace either high or low.
+// Shortcut allows gaps of one rank between cards
+fn is_straight_ranks(ranks: u16, joker_effects: &JokerEffectFlags) -> bool {
+    let max_step = if joker_effects.shortcut { 2 } else { 1 };
+    let ace = 1 << 12;
+
+    let ace_high = ranks << 1;
+    let ace_low = ((ranks & !ace) << 1) | u16::from(ranks & ace != 0);
+
+    is_run(ace_high, max_step) || is_run(ace_low, max_step)
+}
+
+// every hand made by all of the cards at the positions in mask together, one bit..
+// per hand. empty if some of them don't contribute to any hand
+fn hands_made(cards: &[PackedCard], mask: CardMask, joker_effects: &JokerEffectFlags) -> u16 {
+    let size = mask.len();
+    let mut rank_counts = [0u8; 13];
+    let mut ranks = 0u16;
+    let mut suits = u8::MAX;
+
+    for position in mask.positions() {
+        let card = cards[position];
+        rank_counts[card.rank as usize] += 1;
+        ranks |= 1 << card.rank;
+        suits &= card.suits;
+    }
+
+    let distinct = ranks.count_ones() as usize;
+    let largest = rank_counts.iter().copied().max().unwrap_or(0) as usize;
+
+    let flush = size >= pattern_size(joker_effects) && suits != 0;
+    let straight = size >= pattern_size(joker_effects)
+        && distinct == size
+        && is_straight_ranks(ranks, joker_effects);
+    // same rank groups, e.g. a full house is two ranks with the larger group of three
+    let of_a_kind = |count: usize| distinct == 1 && size == count;
+    let five = of_a_kind(5);
+    let full_house = distinct == 2 && size == 5 && largest == 3;
+
+    [
+        (five && flush, PokerHand::FlushFive),
+        (full_house && flush, PokerHand::FlushHouse),
+        (five, PokerHand::FiveOfAKind),
+        (straight && flush, PokerHand::StraightFlush),
+        (of_a_kind(4), PokerHand::FourOfAKind),
+        (full_house, PokerHand::FullHouse),
+        (flush, PokerHand::Flush),
+        (straight, PokerHand::Straight),
+        (of_a_kind(3), PokerHand::ThreeOfAKind),
+        (
+            distinct == 2 && size == 4 && largest == 2,
+            PokerHand::TwoPair,
+        ),
+        (of_a_kind(2), PokerHand::Pair),
+        (of_a_kind(1), PokerHand::HighCard),
+    ]
+    .into_iter()
+    .filter(|&(made, _)| made)
+    .fold(0, |hands, (_, hand)| hands | hand_bit(hand))
+}
+
+// how a subset ranks against others of the same hand and size, compared field by..
+// field in order
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct TieBreakKey {
+    // chips the ranks score
+    rank_chips: u32,
+    // ranks highest first, packed 4 bits each so they compare in order
+    ranks_high_first: u32,
+    // what the cards add besides their ranks, see PackedCard. only breaks ties..
+    // between subsets of the same ranks
+    extra_chips: u64,
+    added_mult: u32,
+    // product of the cards' mult factors. subsets compared have the same size, so..
+    // this orders them by the mult they multiply by
+    mult_factor: u32,
+}
+
+fn tie_break_key(cards: &[PackedCard], mask: CardMask) -> TieBreakKey {
+    let mut ranks = [0u8; MAX_HAND_SIZE];
+    let mut len = 0;
+    let mut rank_chips = 0;
+    let mut extra_chips = 0;
+    let mut added_mult = 0;
+    let mut mult_factor = 1;
+
+    for position in mask.positions().take(MAX_HAND_SIZE) {
+        let card = cards[position];
+        let rank = card.rank;
+        // Two to Ten score their number, face cards 10 and Ace 11
+        rank_chips += match rank {
+            12 => 11,
+            9..=11 => 10,
+            _ => u32::from(rank) + 2,
+        };
+        extra_chips += u64::from(card.extra_chips);
+        added_mult += u32::from(card.added_mult);
+        mult_factor *= u32::from(card.mult_factor);
+
+        // insertion sort, highest rank first
+        let mut index = len;
+        while index > 0 && ranks[index - 1] < rank {
+            ranks[index] = ranks[index - 1];
+            index -= 1;
+        }
+        ranks[index] = rank;
+        len += 1;
+    }
+
+    let ranks_high_first = ranks[..len]
+        .iter()
+        .fold(0, |key, &rank| (key << 4) | u32::from(rank));
+
+    TieBreakKey {
+        rank_chips,
+        ranks_high_first,
+        extra_chips,
+        added_mult,
+        mult_factor,
+    }
+}
+
+// what subsets are compared by: hand, number of cards, then tie_break_key
+type SubsetKey = (PokerHand, usize, TieBreakKey);
+
+// highest hand in a hand set
+fn highest_hand(hands: u16) -> Option<PokerHand> {
+    (hands != 0).then(|| HANDS[(15 - hands.leading_zeros()) as usize])
+}
+
+// the best hand among the played cards and every hand they contain, found once per round.
+// only the first 16 cards are considered
+#[derive(Clone, Copy)]
+pub struct HandAnalysis {
+    pub best_hand: PokerHand,
+    // positions of the cards that make best_hand
+    pub best_hand_mask: CardMask,
+    // positions of the Stone cards, which score whatever the hand
+    pub stone_mask: CardMask,
+    // one bit per hand, set if some of the played cards make it
+    contained: u16,
+}
+
+impl HandAnalysis {
+    // no cards make a high card with no cards and contain nothing
+    pub fn new(cards: &[Card], joker_effects: &JokerEffectFlags) -> Self {
+        let count = cards.len().min(MAX_MASK_CARDS);
+        let mut packed = [PackedCard::default(); MAX_MASK_CARDS];
+        let mut stone_mask = CardMask::default();
+        for (position, (packed_card, card)) in packed.iter_mut().zip(cards).enumerate() {
+            *packed_card = PackedCard::new(card, joker_effects);
+            if card.is_stone() {
+                stone_mask.0 |= 1 << position;
+            }
+        }
+
+        let mut best: Option<(SubsetKey, CardMask)> = None;
+        let mut contained = 0;
+
+        for bits in 1..1u32 << count {
+            let mask = CardMask(bits as u16);
+            if mask.len() > MAX_HAND_SIZE || mask.0 & stone_mask.0 != 0 {
+                continue;
+            }
+
+            let hands = hands_made(&packed[..count], mask, joker_effects);
+            let Some(hand) = highest_hand(hands) else {
+                continue;
+            };
+            contained |= hands;
+
+            // subsets are tried in increasing mask order and only a strictly better one..
+            // replaces the best, so ties keep the earliest
+            let key = (hand, mask.len(), tie_break_key(&packed, mask));
+            if best.is_none_or(|(best_key, _)| key > best_key) {
+                best = Some((key, mask));
+            }
+        }
+
+        let (best_hand, best_hand_mask) = match best {
+            Some(((hand, _, _), mask)) => (hand, mask),
+            None => (PokerHand::HighCard, CardMask::default()),
+        };
+
+        HandAnalysis {
+            best_hand,
+            best_hand_mask,
+            stone_mask,
+            contained,
+        }
+    }
+
+    // the cards that make best_hand, in played order. cards must be the played cards..
+    // the analysis was made from
+    pub fn best_hand_cards(&self, cards: &[Card]) -> CardList {
+        CardList::select(cards, self.best_hand_mask)
+    }
+
+    // the cards that score without Splash: those that make best_hand and every Stone..
+    // card, in played order
+    pub fn scoring_cards(&self, cards: &[Card]) -> CardList {
+        CardList::select(cards, CardMask(self.best_hand_mask.0 | self.stone_mask.0))
+    }
+
+    // checks whether some of the played cards make hand
+    pub fn contains(&self, hand: PokerHand) -> bool {
+        self.contained & hand_bit(hand) != 0
+    }
+}
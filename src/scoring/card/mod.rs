@@ -1,2 +1,5 @@
+pub mod card_mask;
 pub mod card_util;
-pub mod hands;
+pub mod hand_eval;
+pub mod notation;
+pub mod playing_card;
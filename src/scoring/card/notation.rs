@@ -0,0 +1,138 @@
+use crate::scoring::card::playing_card::Enhancement;
+use ortalib::{Rank, Suit};
+
+// File contains the notations accepted when parsing cards, on top of the canonical..
+// form (e.g. "A♦"). all of these are case-insensitive:
+//   ASCII suits:   "AD", "Ah", "10s", "Tc"
+//   long form:     "Ace of Diamonds", "ten of spades"
+//   game keys:     "D_A", "H_K", "S_T"
+// cards are always printed back in the canonical form
+
+// a card entry split into its parts. offsets are byte offsets into the entry
+pub struct CardNotation<'a> {
+    pub rank: &'a str,
+    pub rank_offset: usize,
+    pub suit: &'a str,
+    pub suit_offset: usize,
+    pub modifiers: Vec<(usize, &'a str)>,
+}
+
+// splits an entry on whitespace, keeping the byte offset of each token
+fn tokens_with_offsets(entry: &str) -> Vec<(usize, &str)> {
+    let mut tokens = vec![];
+    let mut token_start = None;
+
+    for (offset, character) in entry.char_indices() {
+        match (character.is_whitespace(), token_start) {
+            (false, None) => token_start = Some(offset),
+            (true, Some(start)) => {
+                tokens.push((start, &entry[start..offset]));
+                token_start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(start) = token_start {
+        tokens.push((start, &entry[start..]));
+    }
+
+    tokens
+}
+
+// finds the rank and suit of a card entry in any accepted notation. everything after..
+// them is left as modifiers. returns None for an empty entry
+pub fn split_card(entry: &str) -> Option<CardNotation<'_>> {
+    let tokens = tokens_with_offsets(entry);
+    let &(first_offset, first) = tokens.first()?;
+
+    // long form, e.g. "Ace of Diamonds Glass"
+    if let [_, (_, of), (suit_offset, suit), ..] = tokens[..]
+        && of.eq_ignore_ascii_case("of")
+    {
+        return Some(CardNotation {
+            rank: first,
+            rank_offset: first_offset,
+            suit,
+            suit_offset,
+            modifiers: tokens[3..].to_vec(),
+        });
+    }
+
+    // game key, e.g. "H_K"
+    let (rank, rank_offset, suit, suit_offset) = match first.split_once('_') {
+        Some((suit, rank)) => (rank, first_offset + suit.len() + 1, suit, first_offset),
+        // suit is always the last character of the first token
+        None => {
+            let suit_start = first.char_indices().last().map_or(0, |(offset, _)| offset);
+            let (rank, suit) = first.split_at(suit_start);
+            (rank, first_offset, suit, first_offset + suit_start)
+        }
+    };
+
+    Some(CardNotation {
+        rank,
+        rank_offset,
+        suit,
+        suit_offset,
+        modifiers: tokens[1..].to_vec(),
+    })
+}
+
+pub fn parse_rank(s: &str) -> Option<Rank> {
+    if let Ok(rank) = s.to_ascii_uppercase().parse() {
+        return Some(rank);
+    }
+
+    #[rustfmt::skip]
+    let rank = match s.to_ascii_lowercase().as_str() {
+        "t" =>     Rank::Ten,
+        "two" =>   Rank::Two,
+        "three" => Rank::Three,
+        "four" =>  Rank::Four,
+        "five" =>  Rank::Five,
+        "six" =>   Rank::Six,
+        "seven" => Rank::Seven,
+        "eight" => Rank::Eight,
+        "nine" =>  Rank::Nine,
+        "ten" =>   Rank::Ten,
+        "jack" =>  Rank::Jack,
+        "queen" => Rank::Queen,
+        "king" =>  Rank::King,
+        "ace" =>   Rank::Ace,
+        _ => return None,
+    };
+
+    Some(rank)
+}
+
+pub fn parse_suit(s: &str) -> Option<Suit> {
+    if let Ok(suit) = s.parse() {
+        return Some(suit);
+    }
+
+    #[rustfmt::skip]
+    let suit = match s.to_ascii_lowercase().as_str() {
+        "s" | "spade" | "spades" =>     Suit::Spades,
+        "h" | "heart" | "hearts" =>     Suit::Hearts,
+        "c" | "club" | "clubs" =>       Suit::Clubs,
+        "d" | "diamond" | "diamonds" => Suit::Diamonds,
+        _ => return None,
+    };
+
+    Some(suit)
+}
+
+pub fn parse_enhancement(s: &str) -> Option<Enhancement> {
+    Enhancement::ALL
+        .into_iter()
+        .find(|enhancement| enhancement.name().eq_ignore_ascii_case(s))
+}
+
+// lowercases a name and drops everything but letters and digits, so "Sock And Buskin",
+// "sock_and_buskin" and "SockAndBuskin" all compare equal
+pub fn fold_name(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|character| character.to_ascii_lowercase())
+        .collect()
+}
@@ -0,0 +1,303 @@
+use crate::scoring::card::notation::{parse_enhancement, parse_rank, parse_suit, split_card};
+use ortalib::{Rank, Suit};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+// File contains the playing card, enhancement and edition types used by the scorer.
+// ortalib's Edition enum skips Negative and its Enhancement enum skips Stone, so the..
+// calculator keeps its own card, enhancement and edition types. ranks and suits..
+// still come from ortalib.
+
+// word that may come before a card's bonus chips, e.g. "K♦ Bonus +25"
+pub const BONUS_LABEL: &str = "Bonus";
+
+// card enhancements
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Enhancement {
+    // +30 chips
+    Bonus,
+    // +4 mult
+    Mult,
+    // counts as every suit
+    Wild,
+    // x2 mult
+    Glass,
+    // x1.5 mult while held in hand
+    Steel,
+    // +50 chips instead of its rank's chips. has no rank or suit, so it's never part..
+    // of a hand, but it always scores
+    Stone,
+}
+
+// card and joker editions
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Edition {
+    Foil,
+    Holographic,
+    Polychrome,
+    // no scoring effect. a Negative joker doesn't take up a joker slot
+    Negative,
+}
+
+// where a card sits in its round, so duplicate cards can still be told apart.
+// cards get their id when a round is loaded and again when it is scored
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash, Debug, Default)]
+pub enum CardId {
+    // not part of a round yet, e.g. a card just created by a tarot
+    #[default]
+    Unassigned,
+    // position in cards_played, from 0
+    Played(usize),
+    // position in cards_held_in_hand, from 0
+    Held(usize),
+}
+
+// a playing card, either played or held in hand
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct Card {
+    pub rank: Rank,
+    pub suit: Suit,
+    pub enhancement: Option<Enhancement>,
+    pub edition: Option<Edition>,
+    // permanent extra chips from effects like Hiker, added whenever the card scores
+    pub bonus_chips: u32,
+    // keeps otherwise identical cards distinct within a round
+    pub id: CardId,
+}
+
+impl Edition {
+    // list of all editions, in the same order as the enum
+    pub const ALL: [Edition; 4] = [
+        Edition::Foil,
+        Edition::Holographic,
+        Edition::Polychrome,
+        Edition::Negative,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Edition::Foil => "Foil",
+            Edition::Holographic => "Holographic",
+            Edition::Polychrome => "Polychrome",
+            Edition::Negative => "Negative",
+        }
+    }
+}
+
+impl Enhancement {
+    // list of all enhancements, in the same order as the enum
+    pub const ALL: [Enhancement; 6] = [
+        Enhancement::Bonus,
+        Enhancement::Mult,
+        Enhancement::Wild,
+        Enhancement::Glass,
+        Enhancement::Steel,
+        Enhancement::Stone,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Enhancement::Bonus => "Bonus",
+            Enhancement::Mult => "Mult",
+            Enhancement::Wild => "Wild",
+            Enhancement::Glass => "Glass",
+            Enhancement::Steel => "Steel",
+            Enhancement::Stone => "Stone",
+        }
+    }
+}
+
+impl Card {
+    pub fn new(
+        rank: Rank,
+        suit: Suit,
+        enhancement: Option<Enhancement>,
+        edition: Option<Edition>,
+    ) -> Self {
+        Self {
+            rank,
+            suit,
+            enhancement,
+            edition,
+            bonus_chips: 0,
+            id: CardId::Unassigned,
+        }
+    }
+
+    // Stone cards keep a rank and suit but don't count as having either
+    pub fn is_stone(&self) -> bool {
+        self.enhancement == Some(Enhancement::Stone)
+    }
+
+    // Jack, Queen or King, unless the card is Stone
+    pub fn is_face(&self) -> bool {
+        !self.is_stone() && self.rank.is_face()
+    }
+}
+
+impl Display for Enhancement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Debug for Enhancement {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for Edition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Debug for Edition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl FromStr for Edition {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Edition::ALL
+            .into_iter()
+            .find(|edition| edition.name().eq_ignore_ascii_case(s))
+            .ok_or_else(|| format!("Invalid Edition: `{s}`"))
+    }
+}
+
+impl Display for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}{}", self.rank, self.suit)?;
+
+        if let Some(enhancement) = self.enhancement {
+            write!(f, " {enhancement}")?;
+        }
+
+        if let Some(edition) = self.edition {
+            write!(f, " {edition}")?;
+        }
+
+        // labelled, so a Bonus card reads back with its enhancement
+        if self.bonus_chips > 0 {
+            write!(f, " {BONUS_LABEL} +{}", self.bonus_chips)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for Card {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl FromStr for Card {
+    type Err = String;
+
+    // rank and suit, then an optional enhancement, an optional edition and..
+    // optional permanent bonus chips. rank and suit can be written in any notation..
+    // from notation.rs, modifiers are case-insensitive.
+    // e.g. "K♦", "K♦ Bonus", "KD negative", "King of Diamonds Bonus Foil", "H_K +25".
+    // "K♦ Bonus +25" is a plain King with 25 bonus chips, "K♦ Bonus Bonus +25" a..
+    // Bonus King with 25 bonus chips
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let notation = split_card(s).ok_or("Cannot parse empty string")?;
+        let mut modifiers: Vec<&str> = notation
+            .modifiers
+            .iter()
+            .map(|&(_, modifier)| modifier)
+            .collect();
+
+        // bonus chips always come last, optionally labelled with the word Bonus. the..
+        // label isn't the Bonus enhancement, which is written before it
+        let mut bonus_chips = 0;
+        if let Some(bonus_str) = modifiers.last().and_then(|last| last.strip_prefix('+')) {
+            bonus_chips = bonus_str
+                .parse()
+                .map_err(|err| format!("Card `{s}` has invalid bonus chips: {err}"))?;
+            modifiers.pop();
+            if modifiers
+                .last()
+                .is_some_and(|last| last.eq_ignore_ascii_case(BONUS_LABEL))
+            {
+                modifiers.pop();
+            }
+        }
+
+        if modifiers.len() > 2 {
+            return Err(format!(
+                "Card `{s}` contains too much data: `{}`",
+                modifiers[2]
+            ));
+        }
+
+        if notation.rank.is_empty() || notation.suit.is_empty() {
+            return Err(format!("Card `{s}` missing rank / suit"));
+        }
+
+        let rank = parse_rank(notation.rank).ok_or_else(|| {
+            format!(
+                "Card `{s}` has invalid rank: Invalid Rank: `{}`",
+                notation.rank
+            )
+        })?;
+
+        let suit = parse_suit(notation.suit).ok_or_else(|| {
+            format!(
+                "Card `{s}` has invalid suit: Invalid Suit: `{}`",
+                notation.suit
+            )
+        })?;
+
+        let (enhancement, edition) = match modifiers.as_slice() {
+            [] => (None, None),
+            [enhancement, edition] => {
+                let enhancement = parse_enhancement(enhancement).ok_or_else(|| {
+                    format!(
+                        "Card `{s}` has invalid enhancement: Invalid Enhancement: `{enhancement}`"
+                    )
+                })?;
+                let edition = edition
+                    .parse::<Edition>()
+                    .map_err(|err| format!("Card `{s}` has invalid edition: {err}"))?;
+                (Some(enhancement), Some(edition))
+            }
+            [modifier] => {
+                if let Some(enhancement) = parse_enhancement(modifier) {
+                    (Some(enhancement), None)
+                } else if let Ok(edition) = modifier.parse::<Edition>() {
+                    (None, Some(edition))
+                } else {
+                    return Err(format!(
+                        "Card `{s}` has invalid enhancement / edition: {modifier}"
+                    ));
+                }
+            }
+            _ => unreachable!("at most two modifiers are allowed"),
+        };
+
+        let mut card = Card::new(rank, suit, enhancement, edition);
+        card.bonus_chips = bonus_chips;
+        Ok(card)
+    }
+}
+
+impl Serialize for Card {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for Card {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
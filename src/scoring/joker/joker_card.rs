@@ -0,0 +1,295 @@
+use crate::scoring::card::notation::fold_name;
+use crate::scoring::card::playing_card::Edition;
+use crate::validation::suggestion::accept_typo;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+// File contains the joker card types used by the scorer.
+// ortalib's Joker enum cannot be extended, so the calculator keeps its own..
+// copy of the joker list which also includes jokers ortalib skips.
+
+// every joker the calculator knows about. variant names match ortalib's
+#[allow(clippy::enum_variant_names)]
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Joker {
+    Joker,
+    JollyJoker,
+    ZanyJoker,
+    MadJoker,
+    CrazyJoker,
+    DrollJoker,
+    SlyJoker,
+    WilyJoker,
+    CleverJoker,
+    DeviousJoker,
+    CraftyJoker,
+    AbstractJoker,
+    RaisedFist,
+    Blackboard,
+    Baron,
+    GreedyJoker,
+    LustyJoker,
+    WrathfulJoker,
+    GluttonousJoker,
+    Fibonacci,
+    ScaryFace,
+    EvenSteven,
+    OddTodd,
+    Photograph,
+    SmileyFace,
+    FlowerPot,
+    FourFingers,
+    Shortcut,
+    Mime,
+    Pareidolia,
+    Splash,
+    SockAndBuskin,
+    SmearedJoker,
+    Blueprint,
+    Brainstorm,
+    Hiker,
+}
+
+// a joker in the joker row along with its edition
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub struct JokerCard {
+    pub joker: Joker,
+    pub edition: Option<Edition>,
+}
+
+impl Joker {
+    // list of all jokers, in the same order as the enum
+    pub const ALL: [Joker; 36] = [
+        Joker::Joker,
+        Joker::JollyJoker,
+        Joker::ZanyJoker,
+        Joker::MadJoker,
+        Joker::CrazyJoker,
+        Joker::DrollJoker,
+        Joker::SlyJoker,
+        Joker::WilyJoker,
+        Joker::CleverJoker,
+        Joker::DeviousJoker,
+        Joker::CraftyJoker,
+        Joker::AbstractJoker,
+        Joker::RaisedFist,
+        Joker::Blackboard,
+        Joker::Baron,
+        Joker::GreedyJoker,
+        Joker::LustyJoker,
+        Joker::WrathfulJoker,
+        Joker::GluttonousJoker,
+        Joker::Fibonacci,
+        Joker::ScaryFace,
+        Joker::EvenSteven,
+        Joker::OddTodd,
+        Joker::Photograph,
+        Joker::SmileyFace,
+        Joker::FlowerPot,
+        Joker::FourFingers,
+        Joker::Shortcut,
+        Joker::Mime,
+        Joker::Pareidolia,
+        Joker::Splash,
+        Joker::SockAndBuskin,
+        Joker::SmearedJoker,
+        Joker::Blueprint,
+        Joker::Brainstorm,
+        Joker::Hiker,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        #[rustfmt::skip]
+        let name = match self {
+            Joker::Joker =>           "Joker",
+            Joker::JollyJoker =>      "Jolly Joker",
+            Joker::ZanyJoker =>       "Zany Joker",
+            Joker::MadJoker =>        "Mad Joker",
+            Joker::CrazyJoker =>      "Crazy Joker",
+            Joker::DrollJoker =>      "Droll Joker",
+            Joker::SlyJoker =>        "Sly Joker",
+            Joker::WilyJoker =>       "Wily Joker",
+            Joker::CleverJoker =>     "Clever Joker",
+            Joker::DeviousJoker =>    "Devious Joker",
+            Joker::CraftyJoker =>     "Crafty Joker",
+            Joker::AbstractJoker =>   "Abstract Joker",
+            Joker::RaisedFist =>      "Raised Fist",
+            Joker::Blackboard =>      "Blackboard",
+            Joker::Baron =>           "Baron",
+            Joker::GreedyJoker =>     "Greedy Joker",
+            Joker::LustyJoker =>      "Lusty Joker",
+            Joker::WrathfulJoker =>   "Wrathful Joker",
+            Joker::GluttonousJoker => "Gluttonous Joker",
+            Joker::Fibonacci =>       "Fibonacci",
+            Joker::ScaryFace =>       "Scary Face",
+            Joker::EvenSteven =>      "Even Steven",
+            Joker::OddTodd =>         "Odd Todd",
+            Joker::Photograph =>      "Photograph",
+            Joker::SmileyFace =>      "Smiley Face",
+            Joker::FlowerPot =>       "Flower Pot",
+            Joker::FourFingers =>     "Four Fingers",
+            Joker::Shortcut =>        "Shortcut",
+            Joker::Mime =>            "Mime",
+            Joker::Pareidolia =>      "Pareidolia",
+            Joker::Splash =>          "Splash",
+            Joker::SockAndBuskin =>   "Sock And Buskin",
+            Joker::SmearedJoker =>    "Smeared Joker",
+            Joker::Blueprint =>       "Blueprint",
+            Joker::Brainstorm =>      "Brainstorm",
+            Joker::Hiker =>           "Hiker",
+        };
+
+        name
+    }
+
+    // the joker's key in Balatro's game files, e.g. "j_blueprint"
+    pub fn key(&self) -> &'static str {
+        #[rustfmt::skip]
+        let key = match self {
+            Joker::Joker =>           "j_joker",
+            Joker::JollyJoker =>      "j_jolly",
+            Joker::ZanyJoker =>       "j_zany",
+            Joker::MadJoker =>        "j_mad",
+            Joker::CrazyJoker =>      "j_crazy",
+            Joker::DrollJoker =>      "j_droll",
+            Joker::SlyJoker =>        "j_sly",
+            Joker::WilyJoker =>       "j_wily",
+            Joker::CleverJoker =>     "j_clever",
+            Joker::DeviousJoker =>    "j_devious",
+            Joker::CraftyJoker =>     "j_crafty",
+            Joker::AbstractJoker =>   "j_abstract",
+            Joker::RaisedFist =>      "j_raised_fist",
+            Joker::Blackboard =>      "j_blackboard",
+            Joker::Baron =>           "j_baron",
+            Joker::GreedyJoker =>     "j_greedy_joker",
+            Joker::LustyJoker =>      "j_lusty_joker",
+            Joker::WrathfulJoker =>   "j_wrathful_joker",
+            Joker::GluttonousJoker => "j_gluttenous_joker",
+            Joker::Fibonacci =>       "j_fibonacci",
+            Joker::ScaryFace =>       "j_scary_face",
+            Joker::EvenSteven =>      "j_even_steven",
+            Joker::OddTodd =>         "j_odd_todd",
+            Joker::Photograph =>      "j_photograph",
+            Joker::SmileyFace =>      "j_smiley",
+            Joker::FlowerPot =>       "j_flower_pot",
+            Joker::FourFingers =>     "j_four_fingers",
+            Joker::Shortcut =>        "j_shortcut",
+            Joker::Mime =>            "j_mime",
+            Joker::Pareidolia =>      "j_pareidolia",
+            Joker::Splash =>          "j_splash",
+            Joker::SockAndBuskin =>   "j_sock_and_buskin",
+            Joker::SmearedJoker =>    "j_smeared",
+            Joker::Blueprint =>       "j_blueprint",
+            Joker::Brainstorm =>      "j_brainstorm",
+            Joker::Hiker =>           "j_hiker",
+        };
+
+        key
+    }
+}
+
+impl JokerCard {
+    pub fn new(joker: Joker, edition: Option<Edition>) -> Self {
+        Self { joker, edition }
+    }
+}
+
+impl Display for Joker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Debug for Joker {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl FromStr for Joker {
+    type Err = String;
+
+    // matches the name or game key ignoring case, spaces and punctuation, so..
+    // "Sock And Buskin", "sock and buskin", "SockAndBuskin" and "j_sock_and_buskin"..
+    // all parse. game keys also work without the "j_", e.g. "zany". a close misspelling..
+    // of one joker's name or key is taken as that joker, e.g. "Jolyl Joker"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let trimmed = s.trim();
+        let without_key_prefix = match trimmed.get(..2) {
+            Some(prefix) if prefix.eq_ignore_ascii_case("j_") => &trimmed[2..],
+            _ => trimmed,
+        };
+        let folded = fold_name(without_key_prefix);
+
+        // every joker's folded name and key
+        let names = || {
+            Joker::ALL.into_iter().flat_map(|joker| {
+                [
+                    (fold_name(joker.name()), joker),
+                    (fold_name(&joker.key()[2..]), joker),
+                ]
+            })
+        };
+
+        names()
+            .find(|(name, _)| *name == folded)
+            .map(|(_, joker)| joker)
+            .or_else(|| accept_typo(&folded, names()))
+            .ok_or_else(|| format!("Invalid Joker: `{s}`"))
+    }
+}
+
+impl Display for JokerCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.joker)?;
+
+        if let Some(edition) = self.edition {
+            write!(f, " {edition}")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Debug for JokerCard {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl FromStr for JokerCard {
+    type Err = String;
+
+    // joker name optionally followed by an edition, e.g. "Brainstorm Foil"
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut joker_str = s.trim();
+        let mut edition = None;
+
+        if let Some((name, suffix)) = joker_str.rsplit_once(' ')
+            && let Ok(parsed_edition) = suffix.parse::<Edition>()
+        {
+            joker_str = name.trim();
+            edition = Some(parsed_edition);
+        }
+
+        let joker = joker_str
+            .parse()
+            .map_err(|err| format!("Invalid JokerCard `{s}`: {err}"))?;
+
+        Ok(JokerCard::new(joker, edition))
+    }
+}
+
+impl Serialize for JokerCard {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for JokerCard {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
@@ -1,6 +1,7 @@
+use crate::scoring::joker::joker_card::{Joker, JokerCard};
 use crate::scoring::joker::joker_wrappers::JokerActivation;
 use crate::scoring::joker::joker_wrappers::JokerEffectFlags;
-use ortalib::{Joker, JokerCard};
+use std::ops::Deref;
 
 // file contains joker related helper functions
 
@@ -35,6 +36,7 @@ pub fn get_joker_activation(joker: &Joker) -> JokerActivation {
         Joker::Photograph => JokerActivation::OnScore,
         Joker::SmileyFace => JokerActivation::OnScore,
         Joker::SockAndBuskin => JokerActivation::OnScore,
+        Joker::Hiker => JokerActivation::OnScore,
 
         // On Held
         Joker::RaisedFist => JokerActivation::OnHeld,
@@ -50,6 +52,7 @@ pub fn get_joker_activation(joker: &Joker) -> JokerActivation {
 
         // Copy
         Joker::Blueprint => JokerActivation::Copy,
+        Joker::Brainstorm => JokerActivation::Copy,
     }
 }
 
@@ -72,56 +75,198 @@ pub fn set_joker_effects(jokers: &[JokerCard]) -> JokerEffectFlags {
     flags
 }
 
+// where a copy joker (Blueprint / Brainstorm) ends up taking its effect from.
+// chain holds the indices visited, starting with the copy joker itself
+pub enum CopyResolution {
+    // chain ended on a joker whose effect can be copied
+    Resolved { source: usize, chain: Vec<usize> },
+    // chain ran off the end of the row or reached a joker that can't be copied
+    NoTarget { chain: Vec<usize> },
+    // chain came back to a joker it already visited
+    Cycle { chain: Vec<usize> },
+}
+
+// returns the index a copy joker points at.
+// Blueprint copies the joker to its right, Brainstorm copies the leftmost joker
+fn copy_target(joker_cards: &[JokerCard], index: usize) -> Option<usize> {
+    match joker_cards[index].joker {
+        Joker::Blueprint => Some(index + 1).filter(|&target| target < joker_cards.len()),
+        Joker::Brainstorm => Some(0),
+        _ => None,
+    }
+}
+
+// how a chain of copies starting at a copy joker ends
+enum CopyEnd {
+    // a joker whose effect can be copied
+    Source(usize),
+    // ran off the end of the row or reached a joker that can't be copied
+    NoTarget,
+    // came back to a joker already visited
+    Cycle,
+}
+
+// follows copy edges from the copy joker at index until a non copy joker is found.
+// e.g. Blueprint -> Brainstorm -> Joker resolves to Joker.
+// visit is called with each joker the chain reaches and returns whether it was..
+// already visited, which ends the chain as a cycle
+fn follow_copies(
+    joker_cards: &[JokerCard],
+    index: usize,
+    mut visit: impl FnMut(usize) -> bool,
+) -> CopyEnd {
+    let mut current = index;
+
+    loop {
+        let Some(target) = copy_target(joker_cards, current) else {
+            return CopyEnd::NoTarget;
+        };
+
+        // revisiting a joker means the copies point at each other
+        if visit(target) {
+            return CopyEnd::Cycle;
+        }
+
+        match get_joker_activation(&joker_cards[target].joker) {
+            // keep following copies of copies
+            JokerActivation::Copy => current = target,
+            // passive jokers can't be copied
+            JokerActivation::Other => return CopyEnd::NoTarget,
+            _ => return CopyEnd::Source(target),
+        }
+    }
+}
+
+// resolves the copy joker at index along with the chain of jokers it went through
+pub fn resolve_copy_source(joker_cards: &[JokerCard], index: usize) -> CopyResolution {
+    let mut chain = vec![index];
+
+    let end = follow_copies(joker_cards, index, |target| {
+        let visited = chain.contains(&target);
+        chain.push(target);
+        visited
+    });
+
+    match end {
+        CopyEnd::Source(source) => CopyResolution::Resolved { source, chain },
+        CopyEnd::NoTarget => CopyResolution::NoTarget { chain },
+        CopyEnd::Cycle => CopyResolution::Cycle { chain },
+    }
+}
+
+// resolves every copy joker in the row. entries are None for jokers that don't copy
+pub fn resolve_copy_graph(joker_cards: &[JokerCard]) -> Vec<Option<CopyResolution>> {
+    (0..joker_cards.len())
+        .map(|index| {
+            if get_joker_activation(&joker_cards[index].joker) == JokerActivation::Copy {
+                Some(resolve_copy_source(joker_cards, index))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// describes a copy resolution for the score trace
+pub fn describe_copy_resolution(joker_cards: &[JokerCard], resolution: &CopyResolution) -> String {
+    let describe_chain = |chain: &[usize]| {
+        chain
+            .iter()
+            .map(|&index| format!("{} (joker {})", joker_cards[index].joker, index + 1))
+            .collect::<Vec<_>>()
+            .join(" -> ")
+    };
+
+    match resolution {
+        CopyResolution::Resolved { source, chain } => format!(
+            "{}: copies {}",
+            describe_chain(chain),
+            joker_cards[*source].joker
+        ),
+        CopyResolution::NoTarget { chain } => {
+            format!("{}: nothing to copy", describe_chain(chain))
+        }
+        CopyResolution::Cycle { chain } => {
+            format!("{}: copy cycle, nothing copied", describe_chain(chain))
+        }
+    }
+}
+
+// returns the index of the joker the copy joker at index takes its effect from, without..
+// building the chain. None when it copies nothing, same as resolve_copy_source
+pub fn copy_source(joker_cards: &[JokerCard], index: usize) -> Option<usize> {
+    // every joker in the row has one copy edge, so a chain longer than the row must..
+    // have come back to a joker it already visited
+    let mut steps = 0;
+    let end = follow_copies(joker_cards, index, |_| {
+        steps += 1;
+        steps > joker_cards.len()
+    });
+
+    match end {
+        CopyEnd::Source(source) => Some(source),
+        CopyEnd::NoTarget | CopyEnd::Cycle => None,
+    }
+}
+
 // takes a list of joker cards and returns a modified version influenced by..
-// blue print jokers.
-pub fn apply_blueprint_jokers(joker_cards: &[JokerCard]) -> Vec<JokerCard> {
-    let mut resolved_jokers = vec![];
-    // Loop through joker cards
-    let mut i = 0;
-    while i < joker_cards.len() {
-        let current_card = &joker_cards[i];
-
-        // ff current card is a Blueprint
-        if matches!(current_card.joker, Joker::Blueprint) {
-            // add the Blueprint joker to the result
-            resolved_jokers.push(current_card.clone());
-
-            // ook to the right for the next valid Joker
-            let mut target_index = i + 1;
-
-            // keep moving right until valid copiable joker found
-            while target_index < joker_cards.len() {
-                match joker_cards[target_index].joker {
-                    // if another Blueprint is found, continue searching further
-                    Joker::Blueprint => {
-                        target_index += 1;
-                    }
-
-                    // if it's an "Other" activation Joker, stop searching
-                    Joker::FourFingers
-                    | Joker::Shortcut
-                    | Joker::Pareidolia
-                    | Joker::Splash
-                    | Joker::SmearedJoker => {
-                        break;
-                    }
-
-                    // if valid Joker found, insert a copy of it right after the Blueprint
-                    // only joker enum is copied. not joker card edition
-                    _ => {
-                        resolved_jokers.push(JokerCard::new(joker_cards[target_index].joker, None));
-                        break;
-                    }
+// copy jokers. each resolved copy is inserted right after the copy joker.
+// only joker enum is copied. not joker card edition
+pub fn resolved_jokers(joker_cards: &[JokerCard]) -> impl Iterator<Item = JokerCard> + '_ {
+    joker_cards
+        .iter()
+        .enumerate()
+        .flat_map(move |(index, joker_card)| {
+            let copy = copy_source(joker_cards, index)
+                .map(|source| JokerCard::new(joker_cards[source].joker, None));
+            std::iter::once(*joker_card).chain(copy)
+        })
+}
+
+// most resolved jokers kept in place, enough for a row of 16 copy jokers and their copies
+const INLINE_JOKERS: usize = 32;
+
+// the joker row with copies resolved, worked out once per round so scoring each card..
+// doesn't follow the copy chains again. kept in place unless the row is too long
+pub struct ResolvedJokers {
+    // only the first len jokers are part of the list, unless spilled holds the row
+    inline: [JokerCard; INLINE_JOKERS],
+    len: usize,
+    spilled: Vec<JokerCard>,
+}
+
+impl ResolvedJokers {
+    pub fn new(joker_cards: &[JokerCard]) -> Self {
+        let mut resolved = ResolvedJokers {
+            inline: [JokerCard::new(Joker::Joker, None); INLINE_JOKERS],
+            len: 0,
+            spilled: Vec::new(),
+        };
+
+        for joker_card in resolved_jokers(joker_cards) {
+            if resolved.len < INLINE_JOKERS {
+                resolved.inline[resolved.len] = joker_card;
+                resolved.len += 1;
+            } else {
+                if resolved.spilled.is_empty() {
+                    resolved.spilled.extend_from_slice(&resolved.inline);
                 }
+                resolved.spilled.push(joker_card);
             }
-        } else {
-            // if it's not a Blueprint, just add it as is
-            resolved_jokers.push(current_card.clone());
         }
 
-        // move to the next card
-        i += 1;
+        resolved
     }
+}
+
+impl Deref for ResolvedJokers {
+    type Target = [JokerCard];
 
-    resolved_jokers
+    fn deref(&self) -> &[JokerCard] {
+        if self.spilled.is_empty() {
+            &self.inline[..self.len]
+        } else {
+            &self.spilled
+        }
+    }
 }
@@ -1,6 +1,7 @@
-use ortalib::{Card, Edition, Joker, JokerCard, Rank, Suit};
-use std::collections::HashMap;
+use crate::scoring::card::hand_eval::HandAnalysis;
+use crate::scoring::card::playing_card::{Card, Edition};
 
+use crate::scoring::joker::joker_card::{Joker, JokerCard};
 use crate::scoring::joker::on_held_joker as OnHeld;
 use crate::scoring::joker::on_independent_joker as Independent;
 use crate::scoring::joker::on_scored_joker as OnScored;
@@ -39,7 +40,7 @@ pub struct JokerOnScoredWrapper {
 
 // container for passive joker effect flags
 // used to determine whether a passive joker effect is active
-#[derive(Default)]
+#[derive(Default, Debug)]
 pub struct JokerEffectFlags {
     pub four_fingers: bool,
     pub shortcut: bool,
@@ -51,17 +52,15 @@ pub struct JokerEffectFlags {
 // define effect function for general joker wrapper
 // returns updated chips and mult after applying joker effect
 impl JokerWrapper {
+    #[allow(clippy::too_many_arguments)]
     pub fn apply_effect(
         &self,
         chips: f64,
         mult: f64,
-        rank_count: &HashMap<Rank, usize>,
-        suit_count: &HashMap<Suit, usize>,
-        wild_count: usize,
-        cards: &[Card],
+        analysis: &HandAnalysis,
         cards_in_hand: &[Card],
         cards_scored: &[Card],
-        joker_cards: &[JokerCard],
+        joker_count: usize,
         joker_effects: &JokerEffectFlags,
     ) -> (f64, f64) {
         let joker_card = &self.joker_card;
@@ -86,52 +85,24 @@ impl JokerWrapper {
             // match joker to specific joker effect function
             match joker_card.joker {
                 Joker::Joker => Independent::joker_effect(&mut updated_mult),
-                Joker::JollyJoker => {
-                    Independent::jolly_joker_effect(&mut updated_mult, rank_count, cards)
-                }
-                Joker::ZanyJoker => {
-                    Independent::zany_joker_effect(&mut updated_mult, rank_count, cards)
-                }
-                Joker::MadJoker => {
-                    Independent::mad_joker_effect(&mut updated_mult, rank_count, cards)
-                }
-                Joker::CrazyJoker => Independent::crazy_joker_effect(
-                    &mut updated_mult,
-                    rank_count,
-                    cards,
-                    joker_effects,
-                ),
-                Joker::DrollJoker => Independent::droll_joker_effect(
-                    &mut updated_mult,
-                    suit_count,
-                    wild_count,
-                    cards,
-                    joker_effects,
-                ),
-                Joker::SlyJoker => {
-                    Independent::sly_joker_effect(&mut updated_chips, rank_count, cards)
+                Joker::JollyJoker => Independent::jolly_joker_effect(&mut updated_mult, analysis),
+                Joker::ZanyJoker => Independent::zany_joker_effect(&mut updated_mult, analysis),
+                Joker::MadJoker => Independent::mad_joker_effect(&mut updated_mult, analysis),
+                Joker::CrazyJoker => Independent::crazy_joker_effect(&mut updated_mult, analysis),
+                Joker::DrollJoker => Independent::droll_joker_effect(&mut updated_mult, analysis),
+                Joker::SlyJoker => Independent::sly_joker_effect(&mut updated_chips, analysis),
+                Joker::WilyJoker => Independent::wily_joker_effect(&mut updated_chips, analysis),
+                Joker::CleverJoker => {
+                    Independent::clever_joker_effect(&mut updated_chips, analysis)
                 }
-                Joker::WilyJoker => {
-                    Independent::wily_joker_effect(&mut updated_chips, rank_count, cards)
+                Joker::DeviousJoker => {
+                    Independent::devious_joker_effect(&mut updated_chips, analysis)
                 }
-                Joker::CleverJoker => {
-                    Independent::clever_joker_effect(&mut updated_chips, rank_count, cards)
+                Joker::CraftyJoker => {
+                    Independent::crafty_joker_effect(&mut updated_chips, analysis)
                 }
-                Joker::DeviousJoker => Independent::devious_joker_effect(
-                    &mut updated_chips,
-                    rank_count,
-                    cards,
-                    joker_effects,
-                ),
-                Joker::CraftyJoker => Independent::crafty_joker_effect(
-                    &mut updated_chips,
-                    suit_count,
-                    wild_count,
-                    cards,
-                    joker_effects,
-                ),
                 Joker::AbstractJoker => {
-                    Independent::abstract_joker_effect(&mut updated_mult, joker_cards)
+                    Independent::abstract_joker_effect(&mut updated_mult, joker_count)
                 }
                 Joker::Blackboard => {
                     Independent::blackboard_effect(&mut updated_mult, cards_in_hand)
@@ -144,13 +115,10 @@ impl JokerWrapper {
         }
 
         // apply polychrome edition bonus
-        if let Some(edition) = joker_card.edition {
-            match edition {
-                Edition::Polychrome => {
-                    updated_mult *= 1.5;
-                }
-                _ => {}
-            }
+        if let Some(edition) = joker_card.edition
+            && edition == Edition::Polychrome
+        {
+            updated_mult *= 1.5;
         }
 
         (updated_chips, updated_mult)
@@ -1,3 +1,4 @@
+pub mod joker_card;
 pub mod joker_util;
 pub mod joker_wrappers;
 pub mod on_held_joker;
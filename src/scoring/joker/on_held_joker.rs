@@ -1,36 +1,37 @@
 use crate::scoring::card::card_util::rank_to_order;
-use ortalib::{Card, Rank};
+use crate::scoring::card::playing_card::Card;
+use ortalib::Rank;
 
 // File contains joker effect functions for specifc "on held" joker cards
 
 pub fn raised_fist_effect(mult: &mut f64, card: &Card, cards: &[Card]) {
-    if cards.is_empty() {
-        return;
-    }
-
-    // find lowest rank
-    let min_rank = cards
+    // find lowest rank, Stone cards have none
+    let Some(min_rank) = cards
         .iter()
+        .filter(|c| !c.is_stone())
         .map(|c| rank_to_order(&c.rank, false))
         .min()
-        .unwrap();
+    else {
+        return;
+    };
 
     // get the right-most card with the lowest rank
     let rightmost_lowest_card = cards
         .iter()
         .rev()
-        .find(|c| rank_to_order(&c.rank, false) == min_rank);
+        .find(|c| !c.is_stone() && rank_to_order(&c.rank, false) == min_rank);
 
-    // update mult if the given card is the right-most lowest-ranked card
-    if let Some(lowest_card) = rightmost_lowest_card {
-        if lowest_card == card {
-            *mult += 2.0 * card.rank.rank_value();
-        }
+    // update mult if the given card is the right-most lowest-ranked card.
+    // compared by id, since a duplicate of that card may also be held
+    if let Some(lowest_card) = rightmost_lowest_card
+        && lowest_card.id == card.id
+    {
+        *mult += 2.0 * card.rank.rank_value();
     }
 }
 
 pub fn baron_effect(mult: &mut f64, card: &Card) {
-    if card.rank == Rank::King {
+    if !card.is_stone() && card.rank == Rank::King {
         *mult *= 1.5;
     }
 }
@@ -1,8 +1,8 @@
-use crate::scoring::card::hands;
+use crate::scoring::card::card_mask::suit_bit;
+use crate::scoring::card::hand_eval::HandAnalysis;
+use crate::scoring::card::playing_card::{Card, Enhancement};
 use crate::scoring::joker::joker_wrappers::JokerEffectFlags;
-use ortalib::{Card, Enhancement, JokerCard, Rank, Suit, SuitColor};
-use std::collections::HashMap;
-use std::collections::HashSet;
+use ortalib::{PokerHand, Suit, SuitColor};
 
 // File contains joker effect functions for specifc "independent" joker cards
 
@@ -10,90 +10,68 @@ pub fn joker_effect(mult: &mut f64) {
     *mult += 4.0;
 }
 
-pub fn jolly_joker_effect(mult: &mut f64, rank_count: &HashMap<Rank, usize>, cards: &[Card]) {
-    if hands::get_pair_cards(cards, rank_count).is_some() {
+pub fn jolly_joker_effect(mult: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::Pair) {
         *mult += 8.0;
     }
 }
 
-pub fn zany_joker_effect(mult: &mut f64, rank_count: &HashMap<Rank, usize>, cards: &[Card]) {
-    if hands::get_three_of_a_kind_cards(cards, rank_count).is_some() {
+pub fn zany_joker_effect(mult: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::ThreeOfAKind) {
         *mult += 12.0;
     }
 }
 
-pub fn mad_joker_effect(mult: &mut f64, rank_count: &HashMap<Rank, usize>, cards: &[Card]) {
-    if hands::get_two_pair_cards(cards, rank_count).is_some() {
+pub fn mad_joker_effect(mult: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::TwoPair) {
         *mult += 10.0;
     }
 }
 
-pub fn crazy_joker_effect(
-    mult: &mut f64,
-    rank_count: &HashMap<Rank, usize>,
-    cards: &[Card],
-    joker_effects: &JokerEffectFlags,
-) {
-    if hands::get_straight_cards(cards, rank_count, joker_effects).is_some() {
+pub fn crazy_joker_effect(mult: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::Straight) {
         *mult += 12.0;
     }
 }
 
-pub fn droll_joker_effect(
-    mult: &mut f64,
-    suit_count: &HashMap<Suit, usize>,
-    wild_count: usize,
-    cards: &[Card],
-    joker_effects: &JokerEffectFlags,
-) {
-    if hands::get_flush_cards(cards, suit_count, wild_count, joker_effects).is_some() {
+pub fn droll_joker_effect(mult: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::Flush) {
         *mult += 10.0;
     }
 }
 
-pub fn sly_joker_effect(chips: &mut f64, rank_count: &HashMap<Rank, usize>, cards: &[Card]) {
-    if hands::get_pair_cards(cards, rank_count).is_some() {
+pub fn sly_joker_effect(chips: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::Pair) {
         *chips += 50.0;
     }
 }
 
-pub fn wily_joker_effect(chips: &mut f64, rank_count: &HashMap<Rank, usize>, cards: &[Card]) {
-    if hands::get_three_of_a_kind_cards(cards, rank_count).is_some() {
+pub fn wily_joker_effect(chips: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::ThreeOfAKind) {
         *chips += 100.0;
     }
 }
 
-pub fn clever_joker_effect(chips: &mut f64, rank_count: &HashMap<Rank, usize>, cards: &[Card]) {
-    if hands::get_two_pair_cards(cards, rank_count).is_some() {
+pub fn clever_joker_effect(chips: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::TwoPair) {
         *chips += 80.0;
     }
 }
 
-pub fn devious_joker_effect(
-    chips: &mut f64,
-    rank_count: &HashMap<Rank, usize>,
-    cards: &[Card],
-    joker_effects: &JokerEffectFlags,
-) {
-    if hands::get_straight_cards(cards, rank_count, joker_effects).is_some() {
+pub fn devious_joker_effect(chips: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::Straight) {
         *chips += 100.0;
     }
 }
 
-pub fn crafty_joker_effect(
-    chips: &mut f64,
-    suit_count: &HashMap<Suit, usize>,
-    wild_count: usize,
-    cards: &[Card],
-    joker_effects: &JokerEffectFlags,
-) {
-    if hands::get_flush_cards(cards, suit_count, wild_count, joker_effects).is_some() {
+pub fn crafty_joker_effect(chips: &mut f64, analysis: &HandAnalysis) {
+    if analysis.contains(PokerHand::Flush) {
         *chips += 80.0;
     }
 }
 
-pub fn abstract_joker_effect(mult: &mut f64, jokers: &[JokerCard]) {
-    *mult += 3.0 * jokers.len() as f64;
+pub fn abstract_joker_effect(mult: &mut f64, joker_count: usize) {
+    *mult += 3.0 * joker_count as f64;
 }
 
 pub fn blackboard_effect(mult: &mut f64, cards: &[Card]) {
@@ -101,9 +79,11 @@ pub fn blackboard_effect(mult: &mut f64, cards: &[Card]) {
         return;
     }
 
+    // Stone cards have no suit, so they're never Spades or Clubs
     if cards.iter().all(|card| {
-        matches!(card.suit, Suit::Spades | Suit::Clubs)
-            || card.enhancement == Some(Enhancement::Wild)
+        !card.is_stone()
+            && (matches!(card.suit, Suit::Spades | Suit::Clubs)
+                || card.enhancement == Some(Enhancement::Wild))
     }) {
         *mult *= 3.0;
     }
@@ -123,6 +103,9 @@ pub fn flower_pot_effect(mult: &mut f64, cards: &[Card], joker_effect: &JokerEff
         let mut red_count = 0;
         let mut black_count = 0;
         for card in cards {
+            if card.is_stone() {
+                continue;
+            }
             if let Some(Enhancement::Wild) = card.enhancement {
                 wild_count += 1;
             } else {
@@ -145,17 +128,20 @@ pub fn flower_pot_effect(mult: &mut f64, cards: &[Card], joker_effect: &JokerEff
 
         valid_cards += red_count + black_count;
     } else {
-        let mut suit_set = HashSet::new();
+        let mut suits = 0u8;
 
         for card in cards {
+            if card.is_stone() {
+                continue;
+            }
             if let Some(Enhancement::Wild) = card.enhancement {
                 wild_count += 1;
             } else {
-                suit_set.insert(card.suit);
+                suits |= suit_bit(card.suit);
             }
         }
 
-        valid_cards += suit_set.len();
+        valid_cards += suits.count_ones();
     }
 
     // if wilds can fill in for missing suits, or if number of valid cards are enough..
@@ -1,3 +1,6 @@
 pub mod card;
 pub mod joker;
+pub mod report;
+pub mod round;
 pub mod scorer;
+pub mod trace;
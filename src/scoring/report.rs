@@ -0,0 +1,37 @@
+use crate::scoring::round::Round;
+use crate::scoring::scorer::{calculate_score_with_trace, identify_hand};
+use crate::scoring::trace::{ScoreTrace, TraceStep};
+use ortalib::{Chips, Mult};
+use serde::Serialize;
+
+// File contains the score summary written as JSON by --output json and the scoring service
+
+#[derive(Serialize)]
+pub struct ScoreReport {
+    pub score: f64,
+    pub hand: String,
+    pub chips: Chips,
+    pub mult: Mult,
+    // every scoring step, when asked for
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub trace: Option<Vec<TraceStep>>,
+}
+
+// scores round, keeping the trace if with_trace is set
+pub fn report_score(round: &Round, with_trace: bool) -> ScoreReport {
+    let (hand, _) = identify_hand(&round.cards_played, &round.jokers);
+    let mut trace = if with_trace {
+        ScoreTrace::default()
+    } else {
+        ScoreTrace::disabled()
+    };
+    let (chips, mult) = calculate_score_with_trace(round.clone(), &mut trace);
+
+    ScoreReport {
+        score: (chips * mult).floor(),
+        hand: hand.to_string(),
+        chips,
+        mult,
+        trace: with_trace.then_some(trace.steps),
+    }
+}
@@ -0,0 +1,141 @@
+use crate::scoring::card::playing_card::{Card, CardId, Edition};
+use crate::scoring::joker::joker_card::JokerCard;
+use serde::{Deserialize, Serialize};
+
+// most cards that can be played in one hand
+pub const MAX_PLAYED_CARDS: usize = 5;
+
+// round read from the input file.
+// mirrors ortalib's Round but uses the calculator's own cards and joker cards
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct Round {
+    pub cards_played: Vec<Card>,
+
+    #[serde(default)]
+    pub cards_held_in_hand: Vec<Card>,
+
+    #[serde(default)]
+    pub jokers: Vec<JokerCard>,
+
+    // number of joker slots available. Negative jokers don't use a slot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub joker_slots: Option<usize>,
+
+    // set when the deck holds one copy of each card, so the same rank and suit..
+    // can't appear twice in the hand
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub unique_cards: bool,
+}
+
+// the part of a round a problem was found in
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RoundLocation {
+    Field(&'static str),
+    PlayedCard(usize),
+    HeldCard(usize),
+}
+
+// a reason the round can't exist in game
+pub struct RoundProblem {
+    pub location: RoundLocation,
+    pub message: String,
+}
+
+impl Round {
+    // gives every played and held card an id from its position, so jokers can..
+    // find a specific card even when the round has duplicates
+    pub fn assign_card_ids(&mut self) {
+        for (index, card) in self.cards_played.iter_mut().enumerate() {
+            card.id = CardId::Played(index);
+        }
+        for (index, card) in self.cards_held_in_hand.iter_mut().enumerate() {
+            card.id = CardId::Held(index);
+        }
+    }
+
+    // returns every reason the round can't exist in game
+    pub fn problems(&self) -> Vec<RoundProblem> {
+        let mut problems = vec![];
+
+        if self.cards_played.is_empty() {
+            problems.push(RoundProblem {
+                location: RoundLocation::Field("cards_played"),
+                message: "No cards played: cards_played must list at least one card".to_string(),
+            });
+        }
+
+        if self.cards_played.len() > MAX_PLAYED_CARDS {
+            problems.push(RoundProblem {
+                location: RoundLocation::PlayedCard(MAX_PLAYED_CARDS),
+                message: format!(
+                    "Too many cards played: {} cards but at most {MAX_PLAYED_CARDS} can be played",
+                    self.cards_played.len()
+                ),
+            });
+        }
+
+        if self.unique_cards {
+            problems.extend(self.duplicate_card_problems());
+        }
+
+        if let Some(joker_slots) = self.joker_slots {
+            let slotted_jokers: Vec<&JokerCard> = self
+                .jokers
+                .iter()
+                .filter(|joker_card| joker_card.edition != Some(Edition::Negative))
+                .collect();
+
+            if slotted_jokers.len() > joker_slots {
+                let negative_count = self.jokers.len() - slotted_jokers.len();
+                problems.push(RoundProblem {
+                    location: RoundLocation::Field("joker_slots"),
+                    message: format!(
+                        "Impossible joker lineup: {} non-Negative jokers but only {} joker slots \
+                         ({} jokers in total, {} of them Negative)",
+                        slotted_jokers.len(),
+                        joker_slots,
+                        self.jokers.len(),
+                        negative_count,
+                    ),
+                });
+            }
+        }
+
+        problems
+    }
+
+    // finds cards that share a rank and suit with an earlier card in the hand
+    fn duplicate_card_problems(&self) -> Vec<RoundProblem> {
+        let played = self
+            .cards_played
+            .iter()
+            .enumerate()
+            .map(|(index, card)| (RoundLocation::PlayedCard(index), card));
+        let held = self
+            .cards_held_in_hand
+            .iter()
+            .enumerate()
+            .map(|(index, card)| (RoundLocation::HeldCard(index), card));
+
+        let mut seen: Vec<&Card> = vec![];
+        let mut problems = vec![];
+
+        for (location, card) in played.chain(held) {
+            if seen
+                .iter()
+                .any(|other| other.rank == card.rank && other.suit == card.suit)
+            {
+                problems.push(RoundProblem {
+                    location,
+                    message: format!(
+                        "Duplicate card {}{}: the deck has only one of each card",
+                        card.rank, card.suit
+                    ),
+                });
+            }
+            seen.push(card);
+        }
+
+        problems
+    }
+}
@@ -1,103 +1,114 @@
-use ortalib::{Card, Chips, Edition, Enhancement, Joker, Mult, PokerHand, Rank, Round, Suit};
-use std::collections::HashMap;
+use ortalib::{Chips, Mult, PokerHand};
 
 use crate::scoring::card::card_util::get_base_score;
 use crate::scoring::card::card_util::get_scoring_cards;
-use crate::scoring::card::hands;
-use crate::scoring::joker::joker_util::apply_blueprint_jokers;
+use crate::scoring::card::hand_eval::HandAnalysis;
+use crate::scoring::card::playing_card::{Card, Edition, Enhancement};
+use crate::scoring::joker::joker_card::{Joker, JokerCard};
+use crate::scoring::joker::joker_util::ResolvedJokers;
+use crate::scoring::joker::joker_util::describe_copy_resolution;
 use crate::scoring::joker::joker_util::get_joker_activation;
+use crate::scoring::joker::joker_util::resolve_copy_graph;
 use crate::scoring::joker::joker_util::set_joker_effects;
 use crate::scoring::joker::joker_wrappers::JokerActivation;
 use crate::scoring::joker::joker_wrappers::JokerEffectFlags;
 use crate::scoring::joker::joker_wrappers::JokerOnHeldWrapper;
 use crate::scoring::joker::joker_wrappers::JokerOnScoredWrapper;
 use crate::scoring::joker::joker_wrappers::JokerWrapper;
+use crate::scoring::round::Round;
+use crate::scoring::trace::ScoreTrace;
+
+// scoring only reads the round and keeps no shared state, so searches can score rounds..
+// on several threads. this fails to compile if a scoring type stops being Send + Sync
+const _: () = {
+    const fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Round>();
+    assert_send_sync::<HandScore>();
+    assert_send_sync::<HandAnalysis>();
+    assert_send_sync::<JokerEffectFlags>();
+    assert_send_sync::<ScoreTrace>();
+};
+
+// final score of a round along with the hand it was scored as
+pub struct HandScore {
+    pub hand: PokerHand,
+    pub score: f64,
+}
+
+// scores a round and finds the hand it was scored as
+pub fn score_round(round: &Round) -> HandScore {
+    let (hand, _) = identify_hand(&round.cards_played, &round.jokers);
+    let (chips, mult) = calculate_score(round.clone());
+
+    HandScore {
+        hand,
+        score: (chips * mult).floor(),
+    }
+}
 
 // Takes a round and calculates score in Chips and Mult
-// Manages broad score calculation logic
 pub fn calculate_score(round: Round) -> (Chips, Mult) {
-    // extract cards frrom round
-    let cards_played = round.cards_played;
-    let cards_held_in_hand = round.cards_held_in_hand;
-    let jokers = round.jokers;
+    calculate_score_with_trace(round, &mut ScoreTrace::disabled())
+}
 
-    // set passive effect jokers flags
-    let joker_effect_flags = set_joker_effects(&jokers);
+// Takes a round and calculates score in Chips and Mult, recording each step in trace
+pub fn calculate_score_with_trace(mut round: Round, trace: &mut ScoreTrace) -> (Chips, Mult) {
+    // cards may have moved or been added since the round was loaded
+    round.assign_card_ids();
 
-    // modify jokers, accounting for blue_print jokers
-    let joker_cards = apply_blueprint_jokers(&jokers);
+    calculate_score_in_place(&round, trace)
+}
 
-    // wrap jokers so that joker effect functions can be implemented for them
-    // <JokerWrapper> implements independent joker effects and edition bonuses for all jokers
-    let wrapped_jokers: Vec<JokerWrapper> = joker_cards
-        .clone()
-        .into_iter()
-        .map(|joker_card| JokerWrapper {
-            joker_activation: get_joker_activation(&joker_card.joker),
-            joker_card,
-        })
-        .collect();
+// calculates score of a round whose card ids are already assigned, e.g. one loaded..
+// through validation. doesn't allocate unless trace is recording
+// Manages broad score calculation logic
+pub fn calculate_score_in_place(round: &Round, trace: &mut ScoreTrace) -> (Chips, Mult) {
+    // extract cards frrom round
+    let cards_played = &round.cards_played;
+    let cards_held_in_hand = &round.cards_held_in_hand;
+    let jokers = &round.jokers;
 
-    // <JokerWrapper> implements on held joker effects
-    let on_held_jokers: Vec<JokerOnHeldWrapper> = joker_cards
-        .clone()
-        .into_iter()
-        .filter(|joker_card| get_joker_activation(&joker_card.joker) == JokerActivation::OnHeld)
-        .map(|joker_card| JokerOnHeldWrapper { joker_card })
-        .collect();
+    // set passive effect jokers flags
+    let joker_effect_flags = set_joker_effects(jokers);
 
-    // <JokerWrapper> implements on scored joker effects
-    let on_scored_jokers: Vec<JokerOnScoredWrapper> = joker_cards
-        .clone()
-        .into_iter()
-        .filter(|joker_card| get_joker_activation(&joker_card.joker) == JokerActivation::OnScore)
-        .map(|joker_card| JokerOnScoredWrapper { joker_card })
-        .collect();
-
-    // count number of cards for each suit and rank. Also count wild cards.
-    // for purpose of finding best hand among played cards
-    let mut rank_count = HashMap::new();
-    let mut suit_count = HashMap::new();
-    let mut wild_count = 0;
-
-    for card in &cards_played {
-        // count rank appearances
-        *rank_count.entry(card.rank).or_insert(0) += 1;
-
-        // if card is wild card then skip suit count entry
-        if let Some(enhancement) = &card.enhancement {
-            if *enhancement == Enhancement::Wild {
-                wild_count += 1;
-                continue;
-            }
+    // resolve what each copy joker (Blueprint / Brainstorm) copies. the full chains..
+    // are only worked out for the trace
+    if trace.is_recording() {
+        for resolution in resolve_copy_graph(jokers).iter().flatten() {
+            trace.note(describe_copy_resolution(jokers, resolution));
         }
-
-        // count suit appearances
-        *suit_count.entry(card.suit).or_insert(0) += 1;
     }
 
-    // Get best hand
-    let (best_hand, best_hand_cards) = find_best_hand(
-        &cards_played,
-        &rank_count,
-        &suit_count,
-        wild_count,
-        &joker_effect_flags,
-    );
+    // Get best hand and every hand contained in the played cards, for the jokers that..
+    // depend on them
+    let analysis = HandAnalysis::new(cards_played, &joker_effect_flags);
+    let best_hand = analysis.best_hand;
+    let best_hand_cards = analysis.best_hand_cards(cards_played);
 
     // Get base chips and mult according to best hand
     let (base_chips, base_mult) = get_base_score(best_hand);
+    trace.step(best_hand, base_chips, base_mult);
 
     // Get scoring cards. Can be different depending on whether Splash joker is active
-    let scoring_cards = get_scoring_cards(&cards_played, &best_hand_cards, &joker_effect_flags);
+    let hand_scoring_cards = analysis.scoring_cards(cards_played);
+    let scoring_cards = get_scoring_cards(cards_played, &hand_scoring_cards, &joker_effect_flags);
+
+    // resolve copies once, every joker below is applied from this row
+    let resolved = ResolvedJokers::new(jokers);
 
     // Get number of triggers for scored and in hand cards
-    let scored_trigger = 1 + joker_cards
+    let scored_trigger = 1 + resolved
         .iter()
         .filter(|joker_card| matches!(joker_card.joker, Joker::SockAndBuskin))
         .count();
 
-    let held_trigger = 1 + joker_cards
+    // each Hiker permanently adds chips to a card every time it is scored
+    let hiker_bonus = 5 * resolved
+        .iter()
+        .filter(|joker_card| matches!(joker_card.joker, Joker::Hiker))
+        .count() as u32;
+
+    let held_trigger = 1 + resolved
         .iter()
         .filter(|joker_card| matches!(joker_card.joker, Joker::Mime))
         .count();
@@ -111,24 +122,38 @@ pub fn calculate_score(round: Round) -> (Chips, Mult) {
 
             // by Sock and Buskin retriggers depend on if card is a face
             // an active pareidolia makes all cards considered a face rank
-            let repeat_count = if card.rank.is_face() || joker_effect_flags.pareidolia {
+            let repeat_count = if card.is_face() || joker_effect_flags.pareidolia {
                 scored_trigger
             } else {
                 1
             };
 
+            // bonus chips grow as Hiker upgrades the card, so retriggers see the upgrade
+            let mut bonus_chips = card.bonus_chips;
+
             // apply card effects multiple times based on repeat_count
             for _ in 0..repeat_count {
                 let (updated_chips, updated_mult) = apply_card(
                     card,
-                    &scoring_cards,
-                    &on_scored_jokers,
+                    bonus_chips,
+                    scoring_cards,
+                    &resolved,
                     new_chips,
                     new_mult,
                     &joker_effect_flags,
                 );
                 new_chips = updated_chips;
                 new_mult = updated_mult;
+                trace.step(format_args!("{card} scored"), new_chips, new_mult);
+
+                if hiker_bonus > 0 {
+                    // a card can't hold more bonus chips than fit, so the upgrade..
+                    // stops there rather than wrapping
+                    bonus_chips = bonus_chips.saturating_add(hiker_bonus);
+                    trace.note(format_args!(
+                        "{card} gains +{hiker_bonus} chips from Hiker (now +{bonus_chips})"
+                    ));
+                }
             }
 
             (new_chips, new_mult)
@@ -143,13 +168,12 @@ pub fn calculate_score(round: Round) -> (Chips, Mult) {
             let mut new_mult = current_mult;
 
             for _ in 0..held_trigger {
-                let (updated_chips, updated_mult) = apply_in_hand_card(
-                    card,
-                    &cards_held_in_hand,
-                    &on_held_jokers,
-                    new_chips,
-                    new_mult,
-                );
+                let (updated_chips, updated_mult) =
+                    apply_in_hand_card(card, cards_held_in_hand, &resolved, new_chips, new_mult);
+                // held cards without an effect are left out of the trace
+                if (updated_chips, updated_mult) != (new_chips, new_mult) {
+                    trace.step(format_args!("{card} held"), updated_chips, updated_mult);
+                }
                 new_chips = updated_chips;
                 new_mult = updated_mult;
             }
@@ -157,99 +181,62 @@ pub fn calculate_score(round: Round) -> (Chips, Mult) {
         },
     );
 
+    // copies count as jokers, e.g. for Abstract Joker
+    let joker_count = resolved.len();
+
     // apply the effects of the independent jokers and edition bonuses on all jokers
-    let (final_chips, final_mult) = wrapped_jokers.iter().fold(
-        (held_chips, held_mult),
-        |(current_chips, current_mult), joker| {
-            joker.apply_effect(
-                current_chips,
-                current_mult,
-                &rank_count,
-                &suit_count,
-                wild_count,
-                &cards_played,
-                &cards_held_in_hand,
-                &best_hand_cards,
-                &joker_cards,
-                &joker_effect_flags,
-            )
-        },
-    );
+    // <JokerWrapper> implements independent joker effects and edition bonuses for all jokers
+    let (final_chips, final_mult) = resolved
+        .iter()
+        .map(|&joker_card| JokerWrapper {
+            joker_activation: get_joker_activation(&joker_card.joker),
+            joker_card,
+        })
+        .fold(
+            (held_chips, held_mult),
+            |(current_chips, current_mult), joker| {
+                let (updated_chips, updated_mult) = joker.apply_effect(
+                    current_chips,
+                    current_mult,
+                    &analysis,
+                    cards_held_in_hand,
+                    &best_hand_cards,
+                    joker_count,
+                    &joker_effect_flags,
+                );
+
+                // jokers without an effect are left out of the trace
+                if (updated_chips, updated_mult) != (current_chips, current_mult) {
+                    trace.step(joker.joker_card, updated_chips, updated_mult);
+                }
+                (updated_chips, updated_mult)
+            },
+        );
 
     // return final chips and mult
     (final_chips, final_mult)
 }
 
-// takes cards and various stats and returns best hand
-pub fn find_best_hand(
-    cards: &[Card],
-    rank_count: &HashMap<Rank, usize>,
-    suit_count: &HashMap<Suit, usize>,
-    wild_count: usize,
-    joker_effects: &JokerEffectFlags,
-) -> (PokerHand, Vec<Card>) {
-    // check for best hand starting from highest scoring
+// takes played cards and jokers and returns best hand, without scoring it
+pub fn identify_hand(cards_played: &[Card], jokers: &[JokerCard]) -> (PokerHand, Vec<Card>) {
+    let joker_effect_flags = set_joker_effects(jokers);
 
-    // check flush five
-    if let Some(flush_five_cards) =
-        hands::get_flush_five_cards(cards, rank_count, suit_count, wild_count, joker_effects)
-    {
-        return (PokerHand::FlushFive, flush_five_cards);
-    }
-    // check flush house
-    else if let Some(flush_house_cards) =
-        hands::get_flush_house_cards(cards, rank_count, suit_count, wild_count, joker_effects)
-    {
-        return (PokerHand::FlushHouse, flush_house_cards);
-    }
-    // check five of a kind
-    else if let Some(five_of_a_kind_cards) =
-        hands::get_five_of_a_kind_cards(cards, rank_count, suit_count)
-    {
-        return (PokerHand::FiveOfAKind, five_of_a_kind_cards);
-    }
-    // check straight flush
-    else if let Some(straight_flush_cards) =
-        hands::get_straight_flush_cards(cards, rank_count, suit_count, wild_count, joker_effects)
-    {
-        return (PokerHand::StraightFlush, straight_flush_cards);
-    // check four of a kind
-    } else if let Some(four_cards) = hands::get_four_of_a_kind_cards(cards, rank_count) {
-        return (PokerHand::FourOfAKind, four_cards);
-    // check full house
-    } else if let Some(full_house_cards) = hands::get_full_house_cards(cards, rank_count) {
-        return (PokerHand::FullHouse, full_house_cards);
-    // check flush
-    } else if let Some(flush_cards) =
-        hands::get_flush_cards(cards, suit_count, wild_count, joker_effects)
-    {
-        return (PokerHand::Flush, flush_cards);
-    // check straight
-    } else if let Some(straight_cards) = hands::get_straight_cards(cards, rank_count, joker_effects)
-    {
-        return (PokerHand::Straight, straight_cards);
-    // check three of a kind
-    } else if let Some(three_cards) = hands::get_three_of_a_kind_cards(cards, rank_count) {
-        return (PokerHand::ThreeOfAKind, three_cards);
-    // check two pairs
-    } else if let Some(two_pair_cards) = hands::get_two_pair_cards(cards, rank_count) {
-        return (PokerHand::TwoPair, two_pair_cards);
-    // check pair
-    } else if let Some(pair_cards) = hands::get_pair_cards(cards, rank_count) {
-        return (PokerHand::Pair, pair_cards);
-    // check high card
-    } else if let Some(high_cards) = hands::get_high_card(cards) {
-        return (PokerHand::HighCard, high_cards);
-    }
+    find_best_hand(cards_played, &joker_effect_flags)
+}
+
+// takes cards and the active joker effects and returns best hand with the cards that make it
+pub fn find_best_hand(cards: &[Card], joker_effects: &JokerEffectFlags) -> (PokerHand, Vec<Card>) {
+    let analysis = HandAnalysis::new(cards, joker_effects);
 
-    (PokerHand::HighCard, vec![])
+    (analysis.best_hand, analysis.best_hand_cards(cards).to_vec())
 }
 
-// function applies base value, enhancements, editions and jokers for scored card
+// function applies base value, bonus chips, enhancements, editions and jokers for scored card
 fn apply_card(
     card: &Card,
+    bonus_chips: u32,
     cards: &[Card],
-    on_scored_jokers: &[JokerOnScoredWrapper],
+    resolved_jokers: &[JokerCard],
     chips: f64,
     mult: f64,
     joker_effects: &JokerEffectFlags,
@@ -257,8 +244,12 @@ fn apply_card(
     let mut updated_chips = chips;
     let mut updated_mult = mult;
 
-    // apply card's rank value to chips
-    updated_chips += card.rank.rank_value();
+    // apply card's rank value and permanent bonus chips to chips. Stone cards have no..
+    // rank, so they score their enhancement's chips instead
+    if !card.is_stone() {
+        updated_chips += card.rank.rank_value();
+    }
+    updated_chips += Chips::from(bonus_chips);
 
     // apply enhancement
     if let Some(enhancement) = &card.enhancement {
@@ -272,6 +263,9 @@ fn apply_card(
             Enhancement::Glass => {
                 updated_mult *= 2.0;
             }
+            Enhancement::Stone => {
+                updated_chips += 50.0;
+            }
             _ => {}
         }
     }
@@ -288,10 +282,17 @@ fn apply_card(
             Edition::Polychrome => {
                 updated_mult *= 1.5;
             }
+            // negative only affects hand size, not score
+            Edition::Negative => {}
         }
     }
 
     // apply "on scored" jokers
+    // <JokerOnScoredWrapper> implements on scored joker effects
+    let on_scored_jokers = resolved_jokers
+        .iter()
+        .filter(|joker_card| get_joker_activation(&joker_card.joker) == JokerActivation::OnScore)
+        .map(|&joker_card| JokerOnScoredWrapper { joker_card });
     for joker in on_scored_jokers {
         let (new_chips, new_mult) =
             joker.apply_effect(card, cards, updated_chips, updated_mult, joker_effects);
@@ -307,7 +308,7 @@ fn apply_card(
 fn apply_in_hand_card(
     card: &Card,
     cards: &[Card],
-    on_held_jokers: &[JokerOnHeldWrapper],
+    resolved_jokers: &[JokerCard],
     chips: f64,
     mult: f64,
 ) -> (f64, f64) {
@@ -315,16 +316,18 @@ fn apply_in_hand_card(
     let mut updated_chips = chips;
 
     // apply enhancement
-    if let Some(enhancement) = &card.enhancement {
-        match enhancement {
-            Enhancement::Steel => {
-                updated_mult *= 1.5;
-            }
-            _ => {}
-        }
+    if let Some(enhancement) = &card.enhancement
+        && enhancement == &Enhancement::Steel
+    {
+        updated_mult *= 1.5;
     }
 
     // apply "on held" jokers
+    // <JokerOnHeldWrapper> implements on held joker effects
+    let on_held_jokers = resolved_jokers
+        .iter()
+        .filter(|joker_card| get_joker_activation(&joker_card.joker) == JokerActivation::OnHeld)
+        .map(|&joker_card| JokerOnHeldWrapper { joker_card });
     for joker in on_held_jokers {
         let (new_chips, new_mult) = joker.apply_effect(card, cards, updated_chips, updated_mult);
         updated_chips = new_chips;
@@ -0,0 +1,71 @@
+use ortalib::{Chips, Mult};
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+// File contains the score trace printed by --explain
+
+// a single line of the trace. chips and mult are the running totals after..
+// the step, notes (e.g. copy resolution) carry no totals
+#[derive(Serialize)]
+pub struct TraceStep {
+    pub description: String,
+    pub totals: Option<(Chips, Mult)>,
+}
+
+// ordered record of every step taken while calculating a score
+#[derive(Default, Serialize)]
+pub struct ScoreTrace {
+    pub steps: Vec<TraceStep>,
+    // set for a trace that records nothing, so scoring doesn't format descriptions
+    #[serde(skip)]
+    disabled: bool,
+}
+
+impl ScoreTrace {
+    // a trace that drops every step, for scoring without --explain
+    pub fn disabled() -> Self {
+        ScoreTrace {
+            steps: vec![],
+            disabled: true,
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        !self.disabled
+    }
+
+    // record a scoring step along with the running chips and mult.
+    // description is only formatted when the trace is recording
+    pub fn step(&mut self, description: impl Display, chips: Chips, mult: Mult) {
+        if self.disabled {
+            return;
+        }
+        self.steps.push(TraceStep {
+            description: description.to_string(),
+            totals: Some((chips, mult)),
+        });
+    }
+
+    // record a step that does not change the score
+    pub fn note(&mut self, description: impl Display) {
+        if self.disabled {
+            return;
+        }
+        self.steps.push(TraceStep {
+            description: description.to_string(),
+            totals: None,
+        });
+    }
+}
+
+impl Display for ScoreTrace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for step in &self.steps {
+            match step.totals {
+                Some((chips, mult)) => writeln!(f, "{} => {} x {}", step.description, chips, mult)?,
+                None => writeln!(f, "{}", step.description)?,
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,148 @@
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::round::{MAX_PLAYED_CARDS, Round};
+use crate::scoring::scorer::{HandScore, calculate_score_in_place, score_round};
+use crate::scoring::trace::ScoreTrace;
+use crate::search::parallel::{SearchOptions, parallel_best};
+
+// File contains the search for the best cards to play out of a hand
+
+// the highest scoring way to play a hand
+pub struct BestPlay {
+    pub round: Round,
+    pub score: HandScore,
+}
+
+// returns every set of k indices out of 0..n, in lexicographic order
+pub fn index_combinations(n: usize, k: usize) -> Vec<Vec<usize>> {
+    let mut combinations = vec![];
+    let mut current = Vec::with_capacity(k);
+    push_combinations(n, k, 0, &mut current, &mut combinations);
+    combinations
+}
+
+fn push_combinations(
+    n: usize,
+    k: usize,
+    start: usize,
+    current: &mut Vec<usize>,
+    combinations: &mut Vec<Vec<usize>>,
+) {
+    if current.len() == k {
+        combinations.push(current.clone());
+        return;
+    }
+
+    for index in start..n {
+        current.push(index);
+        push_combinations(n, k, index + 1, current, combinations);
+        current.pop();
+    }
+}
+
+// number of ways to choose k of n things, saturating at usize::MAX
+pub fn binomial(n: usize, k: usize) -> usize {
+    if k > n {
+        return 0;
+    }
+    let k = k.min(n - k);
+    let mut result: u128 = 1;
+    for i in 0..k {
+        result = result * (n - i) as u128 / (i + 1) as u128;
+        if result > usize::MAX as u128 {
+            return usize::MAX;
+        }
+    }
+    result as usize
+}
+
+// splits hand into the cards at the played indices and the rest, keeping hand order
+pub fn split_hand(hand: &[Card], played: &[usize]) -> (Vec<Card>, Vec<Card>) {
+    let mut cards_played = vec![];
+    let mut cards_held_in_hand = vec![];
+
+    for (index, card) in hand.iter().enumerate() {
+        if played.contains(&index) {
+            cards_played.push(*card);
+        } else {
+            cards_held_in_hand.push(*card);
+        }
+    }
+
+    (cards_played, cards_held_in_hand)
+}
+
+// tries every set of 1 to 5 cards from hand as the played cards, with the rest held.
+// ties keep the first play found, so the result is deterministic
+pub fn find_best_play(hand: &[Card], jokers: &[JokerCard]) -> Option<BestPlay> {
+    find_best_play_with(hand, jokers, &SearchOptions::with_workers(1))
+}
+
+// find_best_play, spreading the plays over the workers in options
+pub fn find_best_play_with(
+    hand: &[Card],
+    jokers: &[JokerCard],
+    options: &SearchOptions,
+) -> Option<BestPlay> {
+    let plays: Vec<Vec<usize>> = (1..=MAX_PLAYED_CARDS.min(hand.len()))
+        .flat_map(|played_count| index_combinations(hand.len(), played_count))
+        .collect();
+
+    let score_play = |played: &Vec<usize>| {
+        let (cards_played, cards_held_in_hand) = split_hand(hand, played);
+        let round = Round {
+            cards_played,
+            cards_held_in_hand,
+            jokers: jokers.to_vec(),
+            ..Round::default()
+        };
+        let score = score_round(&round);
+        Some(BestPlay { round, score })
+    };
+
+    parallel_best(&plays, options, score_play, |play: &BestPlay, best| {
+        play.score.score > best.score.score
+    })
+    .map(|(_, best_play)| best_play)
+}
+
+// highest score of any play of 1 to 5 cards from hand, the score find_best_play finds.
+// it reuses one round for every play instead of keeping each, so it's much cheaper..
+// when only the score is needed. hand must hold fewer than 64 cards
+pub fn best_play_score(hand: &[Card], jokers: &[JokerCard]) -> Option<f64> {
+    best_play_mask(hand, jokers).map(|(score, _)| score)
+}
+
+// best_play_score along with the positions of the played cards, one bit each
+pub fn best_play_mask(hand: &[Card], jokers: &[JokerCard]) -> Option<(f64, u64)> {
+    let mut round = Round {
+        jokers: jokers.to_vec(),
+        ..Round::default()
+    };
+    let mut best: Option<(f64, u64)> = None;
+
+    for played in 1..1u64 << hand.len() {
+        if played.count_ones() as usize > MAX_PLAYED_CARDS {
+            continue;
+        }
+
+        round.cards_played.clear();
+        round.cards_held_in_hand.clear();
+        for (index, card) in hand.iter().enumerate() {
+            if played & (1 << index) != 0 {
+                round.cards_played.push(*card);
+            } else {
+                round.cards_held_in_hand.push(*card);
+            }
+        }
+        round.assign_card_ids();
+
+        let (chips, mult) = calculate_score_in_place(&round, &mut ScoreTrace::disabled());
+        let score = (chips * mult).floor();
+        if best.is_none_or(|(best, _)| score > best) {
+            best = Some((score, played));
+        }
+    }
+
+    best
+}
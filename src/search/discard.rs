@@ -0,0 +1,277 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::card_mask::MAX_MASK_CARDS;
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::round::{MAX_PLAYED_CARDS, Round};
+use crate::scoring::scorer::score_round;
+use crate::search::best_play::{
+    best_play_mask, best_play_score, binomial, index_combinations, split_hand,
+};
+use crate::search::parallel::{SearchOptions, parallel_map};
+use crate::simulate::rng::Rng;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+// File contains the discard advisor, which ranks the cards to discard by the expected..
+// best score after redrawing. each set of 1 to 5 cards is discarded in turn, the same..
+// number of cards is drawn from the remaining deck, and the best play of the new hand..
+// is scored. draws are enumerated exactly when there are few enough of them, and..
+// sampled otherwise. the discards left after the first are played out greedily on..
+// each draw: the cards outside the best play are thrown away and redrawn at random
+
+// what to advise on
+pub struct DiscardSearch {
+    pub hand: Vec<Card>,
+    // cards that can still be drawn
+    pub deck: Vec<Card>,
+    pub jokers: Vec<JokerCard>,
+    // discards left this round. the first is the one advised on, the rest are played..
+    // out greedily after each draw
+    pub discards_left: usize,
+    // score to reach, for the chance of beating it
+    pub target: Option<f64>,
+    // most draws to enumerate exactly, more are sampled
+    pub exact_limit: usize,
+    // draws sampled when there are too many to enumerate
+    pub samples: usize,
+    pub seed: u64,
+}
+
+// one way to discard, with what it is expected to score after redrawing
+#[derive(Serialize)]
+pub struct DiscardOption {
+    // 0-based positions of the discarded cards in the hand
+    pub positions: Vec<usize>,
+    pub cards: Vec<Card>,
+    pub expected_score: f64,
+    // chance the best play after redrawing reaches the target, if one was given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub beat_target: Option<f64>,
+    // number of draws the expectation was taken over
+    pub draws: usize,
+    // set when every possible draw was scored, otherwise draws were sampled
+    pub exact: bool,
+}
+
+// discard options ranked best first, written as JSON by --output json
+#[derive(Serialize)]
+pub struct DiscardAdvice {
+    // best score from playing the hand without discarding
+    pub play_now: f64,
+    pub discards_left: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<f64>,
+    pub options: Vec<DiscardOption>,
+}
+
+impl DiscardSearch {
+    // checks there is something to advise on
+    pub fn validate(&self) -> Result<(), OrtalabError> {
+        if self.hand.is_empty() {
+            return Err(OrtalabError::InvalidArgument(
+                "The hand has no cards to discard".to_string(),
+            ));
+        }
+        if self.discards_left == 0 {
+            return Err(OrtalabError::InvalidArgument(
+                "No discards left: play the best hand instead".to_string(),
+            ));
+        }
+        if self.hand.len() > MAX_MASK_CARDS {
+            return Err(OrtalabError::InvalidArgument(format!(
+                "Too many cards in hand: {} cards but at most {MAX_MASK_CARDS} are supported",
+                self.hand.len()
+            )));
+        }
+        if self.deck.is_empty() {
+            return Err(OrtalabError::InvalidArgument(
+                "The deck is empty, so discarded cards can't be replaced".to_string(),
+            ));
+        }
+        if self.samples == 0 {
+            return Err(OrtalabError::InvalidArgument(
+                "At least one draw must be sampled".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    // best score of hand, 0 if it has no cards
+    fn best_score(&self, hand: &[Card]) -> f64 {
+        best_play_score(hand, &self.jokers).unwrap_or(0.0)
+    }
+
+    // best score of hand after the discards left after the first. each follow-up..
+    // discard throws away up to five cards outside the hand's best play and draws as..
+    // many from deck at random. it stops early when nothing is left to discard or draw,..
+    // or when holding the cards scores more than discarding them
+    fn play_out(&self, mut hand: Vec<Card>, mut deck: Vec<Card>, rng: &mut Rng) -> f64 {
+        let Some((mut score, mut played)) = best_play_mask(&hand, &self.jokers) else {
+            return 0.0;
+        };
+
+        for _ in 1..self.discards_left {
+            let discarded = (0..hand.len())
+                .filter(|&position| played & (1 << position) == 0)
+                .take(MAX_PLAYED_CARDS.min(deck.len()))
+                .fold(0u64, |mask, position| mask | (1 << position));
+            if discarded == 0 {
+                break;
+            }
+
+            // e.g. Baron scores held Kings, so discarding them could lose score
+            let without_discarded = Round {
+                cards_played: select(&hand, played),
+                cards_held_in_hand: select(&hand, !(played | discarded)),
+                jokers: self.jokers.clone(),
+                ..Round::default()
+            };
+            if score_round(&without_discarded).score < score {
+                break;
+            }
+
+            let draw_count = discarded.count_ones() as usize;
+            rng.choose_front(&mut deck, draw_count);
+            hand = select(&hand, !discarded);
+            hand.extend(deck.drain(..draw_count));
+            (score, played) = best_play_mask(&hand, &self.jokers).unwrap_or((0.0, 0));
+        }
+
+        score
+    }
+
+    // scores every draw to replace the cards at positions, or a sample of them.
+    // stream picks the random stream sampled draws and follow-up discards come from
+    fn evaluate(&self, positions: &[usize], stream: u64) -> DiscardOption {
+        let (cards, kept) = split_hand(&self.hand, positions);
+        let draw_count = positions.len();
+        let mut rng = Rng::for_stream(self.seed, stream);
+
+        let exact = binomial(self.deck.len(), draw_count) <= self.exact_limit;
+        let scores: Vec<f64> = if exact {
+            index_combinations(self.deck.len(), draw_count)
+                .iter()
+                .map(|draw| {
+                    let mut hand = kept.clone();
+                    hand.extend(draw.iter().map(|&index| self.deck[index]));
+                    let deck = (0..self.deck.len())
+                        .filter(|index| !draw.contains(index))
+                        .map(|index| self.deck[index])
+                        .collect();
+                    self.play_out(hand, deck, &mut rng)
+                })
+                .collect()
+        } else {
+            let mut deck = self.deck.clone();
+            (0..self.samples)
+                .map(|_| {
+                    rng.choose_front(&mut deck, draw_count);
+                    let mut hand = kept.clone();
+                    hand.extend_from_slice(&deck[..draw_count]);
+                    self.play_out(hand, deck[draw_count..].to_vec(), &mut rng)
+                })
+                .collect()
+        };
+
+        let draws = scores.len();
+        DiscardOption {
+            positions: positions.to_vec(),
+            cards,
+            expected_score: scores.iter().sum::<f64>() / draws as f64,
+            beat_target: self.target.map(|target| {
+                scores.iter().filter(|&&score| score >= target).count() as f64 / draws as f64
+            }),
+            draws,
+            exact,
+        }
+    }
+
+    // evaluates every discard of 1 to 5 cards, spread over the workers in options, and..
+    // ranks them by expected score, then chance of reaching the target. ties keep the..
+    // smaller discard, then the earlier cards
+    pub fn run(&self, options: &SearchOptions) -> DiscardAdvice {
+        let discards: Vec<Vec<usize>> = (1..=MAX_PLAYED_CARDS.min(self.hand.len()))
+            .filter(|&count| count <= self.deck.len())
+            .flat_map(|count| index_combinations(self.hand.len(), count))
+            .collect();
+        let streams: Vec<(u64, &Vec<usize>)> = (0..).zip(&discards).collect();
+
+        let mut ranked = parallel_map(&streams, options, |&(stream, positions)| {
+            self.evaluate(positions, stream)
+        });
+        ranked.sort_by(|a, b| {
+            b.expected_score.total_cmp(&a.expected_score).then(
+                b.beat_target
+                    .unwrap_or(0.0)
+                    .total_cmp(&a.beat_target.unwrap_or(0.0)),
+            )
+        });
+
+        DiscardAdvice {
+            play_now: self.best_score(&self.hand),
+            discards_left: self.discards_left,
+            target: self.target,
+            options: ranked,
+        }
+    }
+}
+
+// the cards of hand at the positions in mask, one bit each, in order
+fn select(hand: &[Card], mask: u64) -> Vec<Card> {
+    hand.iter()
+        .enumerate()
+        .filter(|&(position, _)| mask & (1 << position) != 0)
+        .map(|(_, &card)| card)
+        .collect()
+}
+
+impl DiscardAdvice {
+    // keeps the best count options
+    pub fn truncate(&mut self, count: usize) {
+        self.options.truncate(count);
+    }
+}
+
+impl Display for DiscardAdvice {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Play now: {}, discards left: {}",
+            self.play_now, self.discards_left
+        )?;
+        if let Some(target) = self.target {
+            write!(f, ", target: {target}")?;
+        }
+        writeln!(f)?;
+        writeln!(f)?;
+
+        let target_header = self
+            .target
+            .map_or(String::new(), |target| format!("P(>= {target})"));
+        write!(
+            f,
+            "{:>4}  {:<24} {:>10} {target_header:>12}  Draws",
+            "Rank", "Discard", "Expected"
+        )?;
+
+        for (rank, option) in self.options.iter().enumerate() {
+            let cards: Vec<String> = option.cards.iter().map(ToString::to_string).collect();
+            let beat_target = option
+                .beat_target
+                .map_or(String::new(), |chance| format!("{:.1}%", chance * 100.0));
+            let draws = if option.exact {
+                format!("{} exact", option.draws)
+            } else {
+                format!("{} sampled", option.draws)
+            };
+            write!(
+                f,
+                "\n{:>4}  {:<24} {:>10.1} {beat_target:>12}  {draws}",
+                rank + 1,
+                cards.join(" "),
+                option.expected_score
+            )?;
+        }
+        Ok(())
+    }
+}
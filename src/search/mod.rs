@@ -0,0 +1,3 @@
+pub mod best_play;
+pub mod discard;
+pub mod parallel;
@@ -0,0 +1,128 @@
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::thread;
+
+// File contains the parallel search driver used by the optimisation modes.
+// candidates are handed out to worker threads one at a time, and each worker keeps..
+// the best result it found. results are merged in candidate order, so ties always go..
+// to the earliest candidate and the outcome never depends on thread scheduling
+
+// how a search is run
+#[derive(Clone, Copy, Debug)]
+pub struct SearchOptions {
+    // number of worker threads, 1 searches on the calling thread
+    pub workers: usize,
+}
+
+impl Default for SearchOptions {
+    // one worker per available core
+    fn default() -> Self {
+        SearchOptions {
+            workers: thread::available_parallelism().map_or(1, NonZeroUsize::get),
+        }
+    }
+}
+
+impl SearchOptions {
+    pub fn with_workers(workers: usize) -> Self {
+        SearchOptions {
+            workers: workers.max(1),
+        }
+    }
+}
+
+// evaluates every candidate and returns the results in candidate order
+pub fn parallel_map<T, R>(
+    candidates: &[T],
+    options: &SearchOptions,
+    evaluate: impl Fn(&T) -> R + Sync,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let workers = options.workers.clamp(1, candidates.len().max(1));
+    if workers == 1 {
+        return candidates.iter().map(evaluate).collect();
+    }
+
+    let next_candidate = AtomicUsize::new(0);
+    let work = || {
+        let mut results = vec![];
+        loop {
+            let index = next_candidate.fetch_add(1, Ordering::Relaxed);
+            let Some(candidate) = candidates.get(index) else {
+                return results;
+            };
+            results.push((index, evaluate(candidate)));
+        }
+    };
+
+    let mut results: Vec<(usize, R)> = thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers).map(|_| scope.spawn(work)).collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().expect("search worker panicked"))
+            .collect()
+    });
+
+    results.sort_by_key(|&(index, _)| index);
+    results.into_iter().map(|(_, result)| result).collect()
+}
+
+// evaluates every candidate and returns the index and result of the best one.
+// is_better(a, b) must be true only when a is strictly better than b, so the earliest..
+// of equally good candidates is kept, same as a sequential search
+pub fn parallel_best<T, R>(
+    candidates: &[T],
+    options: &SearchOptions,
+    evaluate: impl Fn(&T) -> Option<R> + Sync,
+    is_better: impl Fn(&R, &R) -> bool + Sync,
+) -> Option<(usize, R)>
+where
+    T: Sync,
+    R: Send,
+{
+    let workers = options.workers.clamp(1, candidates.len().max(1));
+    let next_candidate = AtomicUsize::new(0);
+
+    // each worker takes the next unclaimed candidate until none are left, so its..
+    // candidates are seen in increasing order
+    let search = || {
+        let mut best: Option<(usize, R)> = None;
+        loop {
+            let index = next_candidate.fetch_add(1, Ordering::Relaxed);
+            let Some(candidate) = candidates.get(index) else {
+                return best;
+            };
+            if let Some(result) = evaluate(candidate)
+                && best
+                    .as_ref()
+                    .is_none_or(|(_, current)| is_better(&result, current))
+            {
+                best = Some((index, result));
+            }
+        }
+    };
+
+    let mut worker_bests: Vec<(usize, R)> = if workers == 1 {
+        search().into_iter().collect()
+    } else {
+        thread::scope(|scope| {
+            let handles: Vec<_> = (0..workers).map(|_| scope.spawn(search)).collect();
+            handles
+                .into_iter()
+                .filter_map(|handle| handle.join().expect("search worker panicked"))
+                .collect()
+        })
+    };
+
+    worker_bests.sort_by_key(|&(index, _)| index);
+    worker_bests.into_iter().reduce(|best, candidate| {
+        if is_better(&candidate.1, &best.1) {
+            candidate
+        } else {
+            best
+        }
+    })
+}
@@ -0,0 +1,122 @@
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+
+// File contains just enough HTTP/1.1 for the scoring service: one request per..
+// connection, with the body sized by Content-Length
+
+// largest request body accepted, well over any real round
+const MAX_BODY_BYTES: usize = 1 << 20;
+
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub body: String,
+}
+
+pub struct Response {
+    pub status: u16,
+    pub body: String,
+}
+
+impl Response {
+    pub fn json(status: u16, body: String) -> Self {
+        Self { status, body }
+    }
+
+    // error response with the same shape as the CLI's --output json errors
+    pub fn error(status: u16, kind: &str, message: impl Into<String>) -> Self {
+        let body = serde_json::json!({
+            "error": { "kind": kind, "message": message.into() }
+        });
+        Self::json(status, body.to_string())
+    }
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        422 => "Unprocessable Entity",
+        _ => "Internal Server Error",
+    }
+}
+
+// reads a request. malformed requests are returned as the response to send back
+pub fn read_request(stream: &TcpStream) -> io::Result<Result<Request, Response>> {
+    let mut reader = BufReader::new(stream);
+
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Ok(Err(Response::error(
+            400,
+            "bad_request",
+            "Malformed request line",
+        )));
+    };
+
+    let mut content_length = 0;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':')
+            && name.trim().eq_ignore_ascii_case("content-length")
+        {
+            let Ok(length) = value.trim().parse() else {
+                return Ok(Err(Response::error(
+                    400,
+                    "bad_request",
+                    "Invalid Content-Length",
+                )));
+            };
+            content_length = length;
+        }
+    }
+
+    if content_length > MAX_BODY_BYTES {
+        return Ok(Err(Response::error(
+            413,
+            "bad_request",
+            format!("Request body is over {MAX_BODY_BYTES} bytes"),
+        )));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body)?;
+    let Ok(body) = String::from_utf8(body) else {
+        return Ok(Err(Response::error(
+            400,
+            "bad_request",
+            "Request body is not UTF-8",
+        )));
+    };
+
+    // query strings aren't used by any route
+    let path = path.split('?').next().unwrap_or_default();
+
+    Ok(Ok(Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        body,
+    }))
+}
+
+pub fn write_response(mut stream: &TcpStream, response: &Response) -> io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        response.status,
+        reason_phrase(response.status),
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,164 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::card_mask::MAX_MASK_CARDS;
+use crate::scoring::joker::joker_card::Joker;
+use crate::scoring::report::{ScoreReport, report_score};
+use crate::scoring::round::Round;
+use crate::search::best_play::find_best_play;
+use crate::service::http::{Request, Response, read_request, write_response};
+use crate::validation::round_file::validate_round_source;
+use serde::Serialize;
+use std::io;
+use std::net::{Ipv4Addr, TcpListener, TcpStream};
+use std::panic::catch_unwind;
+use std::time::Duration;
+
+// File contains the local scoring service started by `serve`. it only listens on..
+// localhost. rounds are sent and returned as JSON round objects
+//   POST /score     scores a round
+//   POST /optimize  finds the best play from the round's played and held cards
+//   GET  /jokers    lists every supported joker
+
+// longest a client may take to send its request or read the response. requests are..
+// served one at a time, so a stalled client would otherwise hold up every other
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(10);
+
+// a round along with its score, hand and trace
+#[derive(Serialize)]
+struct ScoredRound<'a> {
+    #[serde(flatten)]
+    round: &'a Round,
+    #[serde(flatten)]
+    report: ScoreReport,
+}
+
+#[derive(Serialize)]
+struct JokerInfo {
+    name: &'static str,
+    key: &'static str,
+}
+
+// serves requests one at a time until the process is stopped
+pub fn serve(port: u16) -> io::Result<()> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, port))?;
+    eprintln!("Listening on http://{}", listener.local_addr()?);
+
+    for stream in listener.incoming() {
+        let result = stream.and_then(|stream| handle_connection(&stream));
+        if let Err(err) = result {
+            eprintln!("Connection error: {err}");
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_connection(stream: &TcpStream) -> io::Result<()> {
+    stream.set_read_timeout(Some(CLIENT_TIMEOUT))?;
+    stream.set_write_timeout(Some(CLIENT_TIMEOUT))?;
+
+    let response = match read_request(stream)? {
+        // requests are served on one thread, so a panic must not unwind out of serve..
+        // and stop it for every client
+        Ok(request) => catch_unwind(|| route(&request)).unwrap_or_else(|_| {
+            Response::error(
+                500,
+                "internal",
+                format!(
+                    "The server panicked handling {} {}",
+                    request.method, request.path
+                ),
+            )
+        }),
+        Err(response) => response,
+    };
+    write_response(stream, &response)
+}
+
+pub fn route(request: &Request) -> Response {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("POST", "/score") => score(&request.body),
+        ("POST", "/optimize") => optimize(&request.body),
+        ("GET", "/jokers") => jokers(),
+        (method, "/score" | "/optimize" | "/jokers") => Response::error(
+            405,
+            "method_not_allowed",
+            format!("{method} is not allowed on {}", request.path),
+        ),
+        (_, path) => Response::error(404, "not_found", format!("No route for {path}")),
+    }
+}
+
+// JSON is a subset of YAML, so request bodies get the same diagnostics as round files
+fn parse_body(body: &str) -> Result<Round, Response> {
+    validate_round_source(body, "<request>").map_err(|err| {
+        let status = match err {
+            OrtalabError::InvalidRound(_) => 422,
+            _ => 400,
+        };
+        Response::json(status, err.to_json())
+    })
+}
+
+fn scored_round_response(round: &Round) -> Response {
+    let scored_round = ScoredRound {
+        round,
+        report: report_score(round, true),
+    };
+    Response::json(
+        200,
+        serde_json::to_string(&scored_round).expect("scored rounds always serialize"),
+    )
+}
+
+fn score(body: &str) -> Response {
+    match parse_body(body) {
+        Ok(round) => scored_round_response(&round),
+        Err(response) => response,
+    }
+}
+
+// the played and held cards together are the hand to choose the best play from
+fn optimize(body: &str) -> Response {
+    let round = match parse_body(body) {
+        Ok(round) => round,
+        Err(response) => return response,
+    };
+    let hand: Vec<_> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .copied()
+        .collect();
+
+    // every play is tried, so larger hands would take too long and too much memory
+    if hand.len() > MAX_MASK_CARDS {
+        return Response::error(
+            422,
+            "invalid_round",
+            format!(
+                "Too many cards in hand: {} cards but at most {MAX_MASK_CARDS} are supported",
+                hand.len()
+            ),
+        );
+    }
+
+    match find_best_play(&hand, &round.jokers) {
+        Some(best_play) => scored_round_response(&best_play.round),
+        None => Response::error(422, "invalid_round", "The round has no cards to play"),
+    }
+}
+
+fn jokers() -> Response {
+    let jokers: Vec<JokerInfo> = Joker::ALL
+        .iter()
+        .map(|joker| JokerInfo {
+            name: joker.name(),
+            key: joker.key(),
+        })
+        .collect();
+
+    Response::json(
+        200,
+        serde_json::to_string(&jokers).expect("jokers always serialize"),
+    )
+}
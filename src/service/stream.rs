@@ -0,0 +1,53 @@
+use crate::error::OrtalabError;
+use crate::scoring::report::report_score;
+use crate::validation::round_file::validate_round_source;
+use std::io::{self, BufRead, Write};
+use std::panic::catch_unwind;
+
+// File contains --stream, which scores one round per input line for as long as input..
+// keeps coming. every non-blank line gets exactly one line of output, either a score..
+// or an error object, so a bad round never ends the stream. that includes a round the..
+// scorer panics on, which gets an internal error instead
+
+pub fn run_stream(input: impl BufRead, output: &mut impl Write) -> io::Result<()> {
+    for (index, line) in input.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let line_number = index + 1;
+        let json = catch_unwind(|| score_line(&line, line_number)).unwrap_or_else(|_| {
+            serde_json::json!({
+                "error": {
+                    "kind": "internal",
+                    "message": format!("The scorer panicked on line {line_number}"),
+                }
+            })
+            .to_string()
+        });
+        writeln!(output, "{json}")?;
+        output.flush()?;
+    }
+
+    Ok(())
+}
+
+// scores a single JSON or flow-YAML round. line_number is used in diagnostics
+fn score_line(line: &str, line_number: usize) -> String {
+    match validate_round_source(line, "<stdin>") {
+        Ok(round) => serde_json::to_string(&report_score(&round, false))
+            .expect("score reports always serialize"),
+        Err(mut err) => {
+            // the round is always on line 1 of its own source
+            if let OrtalabError::Parse(validation_error)
+            | OrtalabError::InvalidRound(validation_error) = &mut err
+            {
+                for diagnostic in &mut validation_error.diagnostics {
+                    diagnostic.line = line_number;
+                }
+            }
+            err.to_json()
+        }
+    }
+}
@@ -0,0 +1,91 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::playing_card::Card;
+use crate::validation::round_flags::parse_card_list;
+use ortalib::{Rank, Suit};
+
+// File contains the decks hands are drawn from in simulations. a deck is either a..
+// named preset or a list of cards written the same way as --play
+
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+
+// names of the preset decks, as accepted by parse_deck
+pub const DECK_PRESETS: [&str; 3] = ["standard", "abandoned", "checkered"];
+
+// one plain card of every rank in ranks for every suit in suits
+fn plain_cards(ranks: &[Rank], suits: &[Suit]) -> Vec<Card> {
+    suits
+        .iter()
+        .flat_map(|&suit| {
+            ranks
+                .iter()
+                .map(move |&rank| Card::new(rank, suit, None, None))
+        })
+        .collect()
+}
+
+// the preset deck called name, if there is one.
+//   standard   52 cards, one of each rank and suit
+//   abandoned  40 cards, the standard deck without face cards
+//   checkered  52 cards, two of each rank in Spades and Hearts
+pub fn preset_deck(name: &str) -> Option<Vec<Card>> {
+    match name.trim().to_lowercase().as_str() {
+        "standard" => Some(plain_cards(&RANKS, &SUITS)),
+        "abandoned" => {
+            let ranks: Vec<Rank> = RANKS.into_iter().filter(|rank| !rank.is_face()).collect();
+            Some(plain_cards(&ranks, &SUITS))
+        }
+        "checkered" => {
+            let suits = [Suit::Spades, Suit::Hearts, Suit::Spades, Suit::Hearts];
+            Some(plain_cards(&RANKS, &suits))
+        }
+        _ => None,
+    }
+}
+
+// reads a deck from a preset name or a list of cards, e.g. "AS KS:Glass QS:Wild".
+// name is used in diagnostics for bad cards
+pub fn parse_deck(name: &'static str, value: &str) -> Result<Vec<Card>, OrtalabError> {
+    let deck = match preset_deck(value) {
+        Some(deck) => deck,
+        None => parse_card_list(name, value).map_err(OrtalabError::Parse)?,
+    };
+
+    if deck.is_empty() {
+        return Err(OrtalabError::InvalidArgument(format!(
+            "The deck given to {name} has no cards. Use one of {} or list its cards",
+            DECK_PRESETS.join(", ")
+        )));
+    }
+    Ok(deck)
+}
+
+// the deck left after drawing cards, removing one card of the same rank and suit for..
+// each. cards that aren't in the deck are ignored
+pub fn remaining_deck(deck: &[Card], cards: &[Card]) -> Vec<Card> {
+    let mut remaining = deck.to_vec();
+    for card in cards {
+        if let Some(position) = remaining
+            .iter()
+            .position(|other| other.rank == card.rank && other.suit == card.suit)
+        {
+            remaining.remove(position);
+        }
+    }
+    remaining
+}
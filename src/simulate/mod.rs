@@ -0,0 +1,4 @@
+pub mod deck;
+pub mod odds;
+pub mod rng;
+pub mod simulation;
@@ -0,0 +1,401 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::card_mask::{rank_index, suit_mask};
+use crate::scoring::card::hand_eval::HANDS;
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::joker::joker_util::set_joker_effects;
+use crate::scoring::joker::joker_wrappers::JokerEffectFlags;
+use crate::search::best_play::binomial;
+use crate::search::parallel::{SearchOptions, parallel_map};
+use ortalib::PokerHand;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+
+// File contains the exact draw odds behind the odds subcommand.
+// every possible draw from the remaining deck is counted, and for each poker hand the..
+// odds are the share of draws where the held and drawn cards together contain it,..
+// with the same rules as hand detection. deck cards with the same rank and suits..
+// (e.g. two Wild Kings) are drawn as a group, weighted by the number of ways to pick..
+// them, so decks with repeated cards need fewer outcomes
+
+// most outcomes counted before the odds are refused as too slow to work out
+pub const MAX_OUTCOMES: u64 = 200_000_000;
+
+// rank and suit counts of a set of cards, enough to tell which hands they contain
+#[derive(Clone, Copy, Default)]
+pub struct HandCounts {
+    // cards of each rank, Two first
+    ranks: [u8; 13],
+    // cards of each rank that count as each suit, in suit mask order
+    suit_ranks: [[u8; 13]; 4],
+}
+
+// rank bits as a run of 14, where bit 0 is a low Ace and bit 13 a high Ace
+fn with_low_ace(rank_bits: u16) -> u16 {
+    (rank_bits << 1) | (rank_bits >> 12 & 1)
+}
+
+// checks whether rank_bits hold length ranks in a row with gaps no wider than max_step.
+// a run can't use an Ace both low and high, since that needs far more than five cards
+fn has_run(rank_bits: u16, length: usize, max_step: u32) -> bool {
+    let mut bits = with_low_ace(rank_bits);
+    let mut previous: Option<u32> = None;
+    let mut run = 0;
+
+    while bits != 0 {
+        let bit = bits.trailing_zeros();
+        run = match previous {
+            Some(previous) if bit - previous <= max_step => run + 1,
+            _ => 1,
+        };
+        if run >= length {
+            return true;
+        }
+        previous = Some(bit);
+        bits &= bits - 1;
+    }
+
+    false
+}
+
+// bits of the ranks with at least count cards
+fn ranks_with(counts: &[u8; 13], count: u8) -> u16 {
+    (0..13)
+        .filter(|&rank| counts[rank] >= count)
+        .fold(0, |bits, rank| bits | 1 << rank)
+}
+
+// checks for a rank with three cards and another with two
+fn has_full_house(counts: &[u8; 13]) -> bool {
+    let threes = ranks_with(counts, 3);
+    let pairs = ranks_with(counts, 2);
+    threes != 0 && (pairs & !threes != 0 || threes.count_ones() >= 2)
+}
+
+impl HandCounts {
+    pub fn from_cards(cards: &[Card], joker_effects: &JokerEffectFlags) -> Self {
+        let mut counts = HandCounts::default();
+        for card in cards.iter().filter(|card| !card.is_stone()) {
+            counts.add(rank_index(&card.rank), suit_mask(card, joker_effects), 1);
+        }
+        counts
+    }
+
+    // adds count cards of rank that count as the suits in suits
+    fn add(&mut self, rank: u8, suits: u8, count: u8) {
+        let rank = rank as usize;
+        self.ranks[rank] += count;
+        for (suit, suit_ranks) in self.suit_ranks.iter_mut().enumerate() {
+            if suits & (1 << suit) != 0 {
+                suit_ranks[rank] += count;
+            }
+        }
+    }
+
+    // every hand some of the cards make, one bit per hand in HANDS order. the same..
+    // hands HandAnalysis::contains finds
+    pub fn contained(&self, joker_effects: &JokerEffectFlags) -> u16 {
+        let needed = if joker_effects.four_fingers { 4 } else { 5 };
+        let max_step = if joker_effects.shortcut { 2 } else { 1 };
+        let rank_bits = ranks_with(&self.ranks, 1);
+        let pairs = ranks_with(&self.ranks, 2);
+
+        let of_suit = |made: &dyn Fn(&[u8; 13]) -> bool| self.suit_ranks.iter().any(made);
+        let suited_cards =
+            |ranks: &[u8; 13]| ranks.iter().map(|&count| usize::from(count)).sum::<usize>();
+
+        [
+            (rank_bits != 0, PokerHand::HighCard),
+            (pairs != 0, PokerHand::Pair),
+            (pairs.count_ones() >= 2, PokerHand::TwoPair),
+            (ranks_with(&self.ranks, 3) != 0, PokerHand::ThreeOfAKind),
+            (has_run(rank_bits, needed, max_step), PokerHand::Straight),
+            (
+                of_suit(&|ranks| suited_cards(ranks) >= needed),
+                PokerHand::Flush,
+            ),
+            (has_full_house(&self.ranks), PokerHand::FullHouse),
+            (ranks_with(&self.ranks, 4) != 0, PokerHand::FourOfAKind),
+            (
+                of_suit(&|ranks| has_run(ranks_with(ranks, 1), needed, max_step)),
+                PokerHand::StraightFlush,
+            ),
+            (ranks_with(&self.ranks, 5) != 0, PokerHand::FiveOfAKind),
+            (of_suit(&has_full_house), PokerHand::FlushHouse),
+            (
+                of_suit(&|ranks| ranks_with(ranks, 5) != 0),
+                PokerHand::FlushFive,
+            ),
+        ]
+        .into_iter()
+        .filter(|&(made, _)| made)
+        .fold(0, |hands, (_, hand)| hands | 1 << hand as u16)
+    }
+
+    // checks whether some of the cards make hand
+    pub fn contains(&self, hand: PokerHand, joker_effects: &JokerEffectFlags) -> bool {
+        self.contained(joker_effects) & 1 << hand as u16 != 0
+    }
+}
+
+// deck cards that are the same for hand detection
+#[derive(Clone, Copy)]
+struct CardGroup {
+    // None for Stone cards, which make no hand
+    rank: Option<u8>,
+    suits: u8,
+    count: usize,
+}
+
+// number of ways each hand was made, weighted by the ways to draw each outcome
+#[derive(Default)]
+struct Tally {
+    makes: [u64; 12],
+    best: [u64; 12],
+    draws: u64,
+}
+
+impl Tally {
+    fn add(&mut self, hands: u16, ways: u64) {
+        for (index, makes) in self.makes.iter_mut().enumerate() {
+            if hands & 1 << index != 0 {
+                *makes += ways;
+            }
+        }
+        if hands != 0 {
+            self.best[15 - hands.leading_zeros() as usize] += ways;
+        }
+        self.draws += ways;
+    }
+
+    fn merge(&mut self, other: &Tally) {
+        for index in 0..HANDS.len() {
+            self.makes[index] += other.makes[index];
+            self.best[index] += other.best[index];
+        }
+        self.draws += other.draws;
+    }
+}
+
+// what to work out the odds for
+pub struct DrawOdds {
+    pub held: Vec<Card>,
+    // cards that can still be drawn
+    pub deck: Vec<Card>,
+    pub jokers: Vec<JokerCard>,
+    // number of cards drawn
+    pub draws: usize,
+}
+
+// odds of one hand. makes is the chance some of the cards make it, best the chance..
+// it's the best hand they make. ways are the number of draws behind each
+#[derive(Serialize)]
+pub struct HandOdds {
+    pub hand: String,
+    pub makes: f64,
+    pub best: f64,
+    pub makes_ways: u64,
+    pub best_ways: u64,
+}
+
+// odds of every hand, best hand first, written as JSON by --output json
+#[derive(Serialize)]
+pub struct OddsReport {
+    pub held: usize,
+    pub deck_size: usize,
+    pub draws: usize,
+    // number of different sets of cards that can be drawn
+    pub total_draws: u64,
+    pub hands: Vec<HandOdds>,
+}
+
+impl DrawOdds {
+    // deck cards grouped by rank and suits, in deck order
+    fn card_groups(&self, joker_effects: &JokerEffectFlags) -> Vec<CardGroup> {
+        let mut groups: Vec<CardGroup> = vec![];
+        for card in &self.deck {
+            let rank = (!card.is_stone()).then(|| rank_index(&card.rank));
+            let suits = suit_mask(card, joker_effects);
+            match groups
+                .iter_mut()
+                .find(|group| group.rank == rank && group.suits == suits)
+            {
+                Some(group) => group.count += 1,
+                None => groups.push(CardGroup {
+                    rank,
+                    suits,
+                    count: 1,
+                }),
+            }
+        }
+        groups
+    }
+
+    // number of outcomes counted, each a number of cards from each group
+    fn outcome_count(groups: &[CardGroup], draws: usize) -> u64 {
+        let mut ways = vec![0u64; draws + 1];
+        ways[0] = 1;
+        for group in groups {
+            for total in (1..=draws).rev() {
+                ways[total] = (1..=group.count.min(total)).fold(ways[total], |sum, count| {
+                    sum.saturating_add(ways[total - count])
+                });
+            }
+        }
+        ways[draws]
+    }
+
+    // checks the odds can be worked out exactly in reasonable time
+    pub fn validate(&self) -> Result<(), OrtalabError> {
+        if self.draws > self.deck.len() {
+            return Err(OrtalabError::InvalidArgument(format!(
+                "Can't draw {} cards from a deck of {}",
+                self.draws,
+                self.deck.len()
+            )));
+        }
+        if self.held.len() + self.draws == 0 {
+            return Err(OrtalabError::InvalidArgument(
+                "No cards held and none drawn".to_string(),
+            ));
+        }
+
+        let joker_effects = set_joker_effects(&self.jokers);
+        let outcomes = Self::outcome_count(&self.card_groups(&joker_effects), self.draws);
+        if outcomes > MAX_OUTCOMES {
+            return Err(OrtalabError::InvalidArgument(format!(
+                "Too many draws to count exactly: drawing {} cards from {} has {outcomes} \
+                 outcomes, at most {MAX_OUTCOMES} are supported",
+                self.draws,
+                self.deck.len()
+            )));
+        }
+        Ok(())
+    }
+
+    // counts every outcome, spread over the workers in options. outcomes are split by the..
+    // first group drawn from
+    pub fn run(&self, options: &SearchOptions) -> OddsReport {
+        let joker_effects = set_joker_effects(&self.jokers);
+        let groups = self.card_groups(&joker_effects);
+        let held = HandCounts::from_cards(&self.held, &joker_effects);
+
+        // cards left in each group and every group after it, to skip hopeless branches
+        let mut cards_from = vec![0; groups.len() + 1];
+        for index in (0..groups.len()).rev() {
+            cards_from[index] = cards_from[index + 1] + groups[index].count;
+        }
+
+        let counter = OutcomeCounter {
+            groups: &groups,
+            cards_from: &cards_from,
+            joker_effects: &joker_effects,
+        };
+
+        let mut tally = Tally::default();
+        if self.draws == 0 {
+            tally.add(held.contained(&joker_effects), 1);
+        } else {
+            let first_groups: Vec<usize> = (0..groups.len()).collect();
+            let branches = parallel_map(&first_groups, options, |&first| {
+                let mut branch = Tally::default();
+                counter.draw_from(first, self.draws, held, 1, &mut branch);
+                branch
+            });
+            for branch in &branches {
+                tally.merge(branch);
+            }
+        }
+        debug_assert_eq!(
+            tally.draws as usize,
+            binomial(self.deck.len(), self.draws),
+            "every draw is counted once"
+        );
+
+        let share = |ways: u64| ways as f64 / tally.draws as f64;
+        let hands = HANDS
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(index, hand)| HandOdds {
+                hand: hand.to_string(),
+                makes: share(tally.makes[index]),
+                best: share(tally.best[index]),
+                makes_ways: tally.makes[index],
+                best_ways: tally.best[index],
+            })
+            .collect();
+
+        OddsReport {
+            held: self.held.len(),
+            deck_size: self.deck.len(),
+            draws: self.draws,
+            total_draws: tally.draws,
+            hands,
+        }
+    }
+}
+
+// walks every outcome of a draw, one group at a time
+struct OutcomeCounter<'a> {
+    groups: &'a [CardGroup],
+    cards_from: &'a [usize],
+    joker_effects: &'a JokerEffectFlags,
+}
+
+impl OutcomeCounter<'_> {
+    // draws at least one card from group and the rest of remaining from later groups.
+    // ways is the number of ways to draw the cards picked so far
+    fn draw_from(
+        &self,
+        group: usize,
+        remaining: usize,
+        counts: HandCounts,
+        ways: u64,
+        tally: &mut Tally,
+    ) {
+        let CardGroup { rank, suits, count } = self.groups[group];
+
+        for drawn in 1..=count.min(remaining) {
+            let mut next = counts;
+            if let Some(rank) = rank {
+                next.add(rank, suits, drawn as u8);
+            }
+            let next_ways = ways * binomial(count, drawn) as u64;
+
+            if drawn == remaining {
+                tally.add(next.contained(self.joker_effects), next_ways);
+                continue;
+            }
+            for later in group + 1..self.groups.len() {
+                if self.cards_from[later] < remaining - drawn {
+                    break;
+                }
+                self.draw_from(later, remaining - drawn, next, next_ways, tally);
+            }
+        }
+    }
+}
+
+impl Display for OddsReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Drawing {} of {} cards to {} held, {} possible draws",
+            self.draws, self.deck_size, self.held, self.total_draws
+        )?;
+        writeln!(f)?;
+        write!(f, "{:<18} {:>9} {:>11}", "Hand", "Makes", "Best hand")?;
+
+        for odds in &self.hands {
+            write!(
+                f,
+                "\n{:<18} {:>8.3}% {:>10.3}%",
+                odds.hand,
+                odds.makes * 100.0,
+                odds.best * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+// File contains the seeded random number generator used by the simulators.
+// it is SplitMix64, which is small, fast and good enough for drawing cards. every..
+// simulated hand gets its own stream from the seed and its index, so results don't..
+// depend on how the hands are spread over worker threads
+
+const GOLDEN_GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+
+// scrambles a 64 bit value, the SplitMix64 output function
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
+
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng { state: seed }
+    }
+
+    // the generator for stream index of seed, e.g. one per simulated hand
+    pub fn for_stream(seed: u64, index: u64) -> Self {
+        Rng::new(mix(seed ^ mix(index.wrapping_add(GOLDEN_GAMMA))))
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(GOLDEN_GAMMA);
+        mix(self.state)
+    }
+
+    // a number in 0..bound. bound must not be 0
+    pub fn below(&mut self, bound: usize) -> usize {
+        ((u128::from(self.next_u64()) * bound as u128) >> 64) as usize
+    }
+
+    // moves count random items of items to the front, in random order.
+    // the rest are left in some order
+    pub fn choose_front<T>(&mut self, items: &mut [T], count: usize) {
+        for index in 0..count.min(items.len()) {
+            let other = index + self.below(items.len() - index);
+            items.swap(index, other);
+        }
+    }
+}
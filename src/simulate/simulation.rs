@@ -0,0 +1,324 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::card_mask::MAX_MASK_CARDS;
+use crate::scoring::card::hand_eval::{HANDS, HandAnalysis};
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::joker::joker_util::set_joker_effects;
+use crate::scoring::round::Round;
+use crate::scoring::scorer::{HandScore, score_round};
+use crate::search::best_play::{find_best_play, split_hand};
+use crate::search::parallel::{SearchOptions, parallel_map};
+use crate::simulate::rng::Rng;
+use serde::Serialize;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+// File contains the Monte Carlo draw simulator behind the simulate subcommand.
+// random hands are drawn from a deck, the cards to play are picked by a play policy..
+// and scored, and the scores and hands played are summarised. hand i is always drawn..
+// from stream i of the seed, so a seed gives the same report with any worker count
+
+// percentiles reported for the score distribution
+const PERCENTILES: [u8; 5] = [10, 25, 50, 75, 90];
+
+// number of equal width buckets in the score histogram
+const HISTOGRAM_BUCKETS: usize = 10;
+
+// width of the longest histogram bar in the text report
+const BAR_WIDTH: usize = 40;
+
+// how the cards to play are picked from a drawn hand
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PlayPolicy {
+    // every set of 1 to 5 cards is scored and the highest scoring one is played
+    BestScore,
+    // the cards making the best poker hand are played, without scoring the others
+    BestHand,
+}
+
+impl PlayPolicy {
+    pub const ALL: [PlayPolicy; 2] = [PlayPolicy::BestScore, PlayPolicy::BestHand];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlayPolicy::BestScore => "best-score",
+            PlayPolicy::BestHand => "best-hand",
+        }
+    }
+}
+
+impl Display for PlayPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl FromStr for PlayPolicy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        PlayPolicy::ALL
+            .into_iter()
+            .find(|policy| policy.name() == s.trim())
+            .ok_or_else(|| {
+                let names: Vec<&str> = PlayPolicy::ALL.iter().map(|policy| policy.name()).collect();
+                format!(
+                    "Invalid play policy: `{s}`. Expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
+
+// what to simulate
+pub struct Simulation {
+    pub deck: Vec<Card>,
+    pub hand_size: usize,
+    pub jokers: Vec<JokerCard>,
+    pub policy: PlayPolicy,
+    // number of hands to draw
+    pub hands: usize,
+    pub seed: u64,
+}
+
+#[derive(Serialize)]
+pub struct Percentile {
+    pub percent: u8,
+    pub score: f64,
+}
+
+// scores from `from` up to `to`. the last bucket also holds scores equal to `to`
+#[derive(Serialize)]
+pub struct HistogramBucket {
+    pub from: f64,
+    pub to: f64,
+    pub count: usize,
+}
+
+#[derive(Serialize)]
+pub struct ScoreDistribution {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    pub percentiles: Vec<Percentile>,
+    pub histogram: Vec<HistogramBucket>,
+}
+
+// how often a hand type was played, share is the fraction of all hands
+#[derive(Serialize)]
+pub struct HandFrequency {
+    pub hand: String,
+    pub count: usize,
+    pub share: f64,
+}
+
+// summary of a simulation, written as JSON by --output json
+#[derive(Serialize)]
+pub struct SimulationReport {
+    pub hands: usize,
+    pub hand_size: usize,
+    pub deck_size: usize,
+    pub policy: String,
+    pub seed: u64,
+    pub scores: ScoreDistribution,
+    // hand types played at least once, best hand first
+    pub hand_types: Vec<HandFrequency>,
+}
+
+impl Simulation {
+    // checks the simulation can be run, e.g. that the deck has enough cards to draw
+    pub fn validate(&self) -> Result<(), OrtalabError> {
+        if self.hands == 0 {
+            return Err(OrtalabError::InvalidArgument(
+                "At least one hand must be simulated".to_string(),
+            ));
+        }
+        if !(1..=MAX_MASK_CARDS).contains(&self.hand_size) {
+            return Err(OrtalabError::InvalidArgument(format!(
+                "Hand size {} is out of range: hands hold 1 to {MAX_MASK_CARDS} cards",
+                self.hand_size
+            )));
+        }
+        if self.hand_size > self.deck.len() {
+            return Err(OrtalabError::InvalidArgument(format!(
+                "Can't draw {} cards from a deck of {}",
+                self.hand_size,
+                self.deck.len()
+            )));
+        }
+        Ok(())
+    }
+
+    // the hand drawn for simulated hand index
+    pub fn draw_hand(&self, index: u64) -> Vec<Card> {
+        let mut rng = Rng::for_stream(self.seed, index);
+        let mut deck = self.deck.clone();
+        rng.choose_front(&mut deck, self.hand_size);
+        deck.truncate(self.hand_size);
+        deck
+    }
+
+    // picks the cards to play from hand with the play policy and scores them
+    fn play(&self, hand: &[Card]) -> HandScore {
+        match self.policy {
+            PlayPolicy::BestScore => {
+                find_best_play(hand, &self.jokers)
+                    .expect("hands hold at least one card")
+                    .score
+            }
+            PlayPolicy::BestHand => {
+                let joker_effects = set_joker_effects(&self.jokers);
+                let analysis = HandAnalysis::new(hand, &joker_effects);
+                let played: Vec<usize> = analysis.best_hand_mask.positions().collect();
+                let (cards_played, cards_held_in_hand) = split_hand(hand, &played);
+
+                score_round(&Round {
+                    cards_played,
+                    cards_held_in_hand,
+                    jokers: self.jokers.clone(),
+                    ..Round::default()
+                })
+            }
+        }
+    }
+
+    // draws and plays every hand, spread over the workers in options
+    pub fn run(&self, options: &SearchOptions) -> SimulationReport {
+        let indices: Vec<u64> = (0..self.hands as u64).collect();
+        let results = parallel_map(&indices, options, |&index| {
+            self.play(&self.draw_hand(index))
+        });
+
+        let scores: Vec<f64> = results.iter().map(|result| result.score).collect();
+        let hand_types = HANDS
+            .into_iter()
+            .rev()
+            .filter_map(|hand| {
+                let count = results.iter().filter(|result| result.hand == hand).count();
+                (count > 0).then(|| HandFrequency {
+                    hand: hand.to_string(),
+                    count,
+                    share: count as f64 / results.len() as f64,
+                })
+            })
+            .collect();
+
+        SimulationReport {
+            hands: self.hands,
+            hand_size: self.hand_size,
+            deck_size: self.deck.len(),
+            policy: self.policy.to_string(),
+            seed: self.seed,
+            scores: ScoreDistribution::new(scores),
+            hand_types,
+        }
+    }
+}
+
+impl ScoreDistribution {
+    // summarises scores, which must not be empty
+    fn new(mut scores: Vec<f64>) -> Self {
+        scores.sort_by(f64::total_cmp);
+        let count = scores.len() as f64;
+        let mean = scores.iter().sum::<f64>() / count;
+        let variance = scores
+            .iter()
+            .map(|score| (score - mean).powi(2))
+            .sum::<f64>()
+            / count;
+        let min = scores[0];
+        let max = scores[scores.len() - 1];
+
+        // nearest rank: the smallest score with at least percent% of scores at or below it
+        let percentiles = PERCENTILES
+            .into_iter()
+            .map(|percent| {
+                let rank = (f64::from(percent) / 100.0 * count).ceil() as usize;
+                Percentile {
+                    percent,
+                    score: scores[rank.clamp(1, scores.len()) - 1],
+                }
+            })
+            .collect();
+
+        let bucket_count = if max > min { HISTOGRAM_BUCKETS } else { 1 };
+        let width = (max - min) / bucket_count as f64;
+        let mut histogram: Vec<HistogramBucket> = (0..bucket_count)
+            .map(|bucket| HistogramBucket {
+                from: min + width * bucket as f64,
+                to: min + width * (bucket + 1) as f64,
+                count: 0,
+            })
+            .collect();
+        for score in &scores {
+            let bucket = if width > 0.0 {
+                (((score - min) / width) as usize).min(bucket_count - 1)
+            } else {
+                0
+            };
+            histogram[bucket].count += 1;
+        }
+
+        ScoreDistribution {
+            mean,
+            std_dev: variance.sqrt(),
+            min,
+            max,
+            percentiles,
+            histogram,
+        }
+    }
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "{} hands of {} cards from a {} card deck, policy {}, seed {}",
+            self.hands, self.hand_size, self.deck_size, self.policy, self.seed
+        )?;
+
+        let scores = &self.scores;
+        writeln!(f)?;
+        writeln!(
+            f,
+            "Score: mean {:.1}, std dev {:.1}, min {}, max {}",
+            scores.mean, scores.std_dev, scores.min, scores.max
+        )?;
+        let percentiles: Vec<String> = scores
+            .percentiles
+            .iter()
+            .map(|percentile| format!("{}%: {}", percentile.percent, percentile.score))
+            .collect();
+        writeln!(f, "Percentiles: {}", percentiles.join(", "))?;
+
+        writeln!(f)?;
+        writeln!(f, "Distribution:")?;
+        let most = scores
+            .histogram
+            .iter()
+            .map(|bucket| bucket.count)
+            .max()
+            .unwrap_or(0)
+            .max(1);
+        for bucket in &scores.histogram {
+            let range = format!("{:.0} to {:.0}", bucket.from, bucket.to);
+            let bar = "#".repeat(bucket.count * BAR_WIDTH / most);
+            writeln!(f, "  {range:<22} {:>7}  {bar}", bucket.count)?;
+        }
+
+        writeln!(f)?;
+        write!(f, "Hands played:")?;
+        for frequency in &self.hand_types {
+            write!(
+                f,
+                "\n  {:<22} {:>7}  {:5.1}%",
+                frequency.hand,
+                frequency.count,
+                frequency.share * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
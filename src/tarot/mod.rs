@@ -0,0 +1,3 @@
+pub mod target_search;
+pub mod tarot_card;
+pub mod what_if;
@@ -0,0 +1,177 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::card_mask::MAX_MASK_CARDS;
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::search::best_play::{BestPlay, find_best_play, index_combinations};
+use crate::search::parallel::{SearchOptions, parallel_best};
+use crate::tarot::tarot_card::Tarot;
+use std::fmt::{Display, Formatter};
+
+// File contains the tarot target search, which finds the best cards to use held..
+// tarots on. tarots are used in the order given, then the best play is made from..
+// the updated hand
+
+// one tarot used on a set of cards. targets are 0-based indices into the hand
+#[derive(Clone)]
+pub struct TarotUse {
+    pub tarot: Tarot,
+    pub targets: Vec<usize>,
+    // each targeted card before and after the tarot
+    pub changed_cards: Vec<(Card, Card)>,
+}
+
+// most allocations a search scores. each one runs a full best play search, so more..
+// would take too long
+pub const MAX_TARGET_ALLOCATIONS: usize = 50_000;
+
+// the best allocation of tarots found by the search
+pub struct TargetSearchResult {
+    pub tarot_uses: Vec<TarotUse>,
+    pub best_play: BestPlay,
+    // best play from the hand without using any tarots
+    pub baseline: Option<BestPlay>,
+}
+
+// every legal set of targets for tarot in a hand of hand_size cards. cards can be..
+// moved around the hand before using a tarot, so Death tries both cards of each pair..
+// as the one that becomes a copy of the other
+pub fn legal_target_sets(tarot: Tarot, hand_size: usize) -> Vec<Vec<usize>> {
+    let (min_targets, max_targets) = tarot.target_range();
+
+    (min_targets..=max_targets.min(hand_size))
+        .flat_map(|target_count| index_combinations(hand_size, target_count))
+        .flat_map(|targets| {
+            let reversed: Option<Vec<usize>> =
+                (tarot == Tarot::Death).then(|| targets.iter().rev().copied().collect());
+            std::iter::once(targets).chain(reversed)
+        })
+        .collect()
+}
+
+// number of allocations of tarots to a hand of hand_size cards, the product of each..
+// tarot's legal target sets. saturates at usize::MAX
+pub fn allocation_count(hand_size: usize, tarots: &[Tarot]) -> usize {
+    tarots.iter().fold(1, |count: usize, &tarot| {
+        count.saturating_mul(legal_target_sets(tarot, hand_size).len())
+    })
+}
+
+// tries every legal target set for each tarot and returns the allocation with the..
+// highest best play score. allocations are scored on the workers in options, and ties..
+// keep the first allocation in search order. hands over 16 cards or more than..
+// MAX_TARGET_ALLOCATIONS allocations are refused, since they would take too long
+pub fn search_tarot_targets(
+    hand: &[Card],
+    jokers: &[JokerCard],
+    tarots: &[Tarot],
+    options: &SearchOptions,
+) -> Result<Option<TargetSearchResult>, OrtalabError> {
+    if hand.len() > MAX_MASK_CARDS {
+        return Err(OrtalabError::InvalidArgument(format!(
+            "Too many cards in hand: {} cards but at most {MAX_MASK_CARDS} are supported",
+            hand.len()
+        )));
+    }
+    let count = allocation_count(hand.len(), tarots);
+    if count > MAX_TARGET_ALLOCATIONS {
+        return Err(OrtalabError::InvalidArgument(format!(
+            "Too many ways to use the tarots: {count} target allocations but at most \
+             {MAX_TARGET_ALLOCATIONS} are searched. Use fewer tarots or a smaller hand"
+        )));
+    }
+
+    let mut allocations = vec![];
+    collect_allocations(hand, tarots, &mut vec![], &mut allocations);
+
+    let best = parallel_best(
+        &allocations,
+        options,
+        |(_, updated_hand)| find_best_play(updated_hand, jokers),
+        |play: &BestPlay, best| play.score.score > best.score.score,
+    );
+
+    Ok(best.map(|(index, best_play)| TargetSearchResult {
+        tarot_uses: allocations.swap_remove(index).0,
+        best_play,
+        baseline: find_best_play(hand, jokers),
+    }))
+}
+
+// uses the first remaining tarot on every legal target set and recurses on the rest.
+// once every tarot is used, the tarot uses and the updated hand are added to allocations
+fn collect_allocations(
+    hand: &[Card],
+    tarots: &[Tarot],
+    tarot_uses: &mut Vec<TarotUse>,
+    allocations: &mut Vec<(Vec<TarotUse>, Vec<Card>)>,
+) {
+    let Some((&tarot, remaining_tarots)) = tarots.split_first() else {
+        allocations.push((tarot_uses.clone(), hand.to_vec()));
+        return;
+    };
+
+    for targets in legal_target_sets(tarot, hand.len()) {
+        let mut updated_hand = hand.to_vec();
+        tarot
+            .apply(&mut updated_hand, &targets)
+            .expect("legal target sets fit the tarot");
+
+        let changed_cards = targets
+            .iter()
+            .map(|&index| (hand[index], updated_hand[index]))
+            .collect();
+        tarot_uses.push(TarotUse {
+            tarot,
+            targets,
+            changed_cards,
+        });
+
+        collect_allocations(&updated_hand, remaining_tarots, tarot_uses, allocations);
+        tarot_uses.pop();
+    }
+}
+
+fn join_cards(cards: &[Card]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+impl Display for TargetSearchResult {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for tarot_use in &self.tarot_uses {
+            let positions: Vec<String> = tarot_use
+                .targets
+                .iter()
+                .map(|index| (index + 1).to_string())
+                .collect();
+            writeln!(f, "{} on {}:", tarot_use.tarot, positions.join(", "))?;
+
+            for (before, after) in &tarot_use.changed_cards {
+                writeln!(f, "  {before} -> {after}")?;
+            }
+        }
+
+        let round = &self.best_play.round;
+        writeln!(
+            f,
+            "Play: {} ({})",
+            join_cards(&round.cards_played),
+            self.best_play.score.hand
+        )?;
+        writeln!(f, "Hold: {}", join_cards(&round.cards_held_in_hand))?;
+        write!(f, "Score: {}", self.best_play.score.score)?;
+
+        if let Some(baseline) = &self.baseline {
+            write!(
+                f,
+                " ({:+} over the best play without tarots)",
+                self.best_play.score.score - baseline.score.score
+            )?;
+        }
+
+        Ok(())
+    }
+}
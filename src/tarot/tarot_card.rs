@@ -0,0 +1,179 @@
+use crate::scoring::card::notation::fold_name;
+use crate::scoring::card::playing_card::{Card, Enhancement};
+use ortalib::{Rank, Suit};
+use std::fmt::{Debug, Display, Formatter};
+use std::str::FromStr;
+
+// File contains the tarot cards that modify playing cards and how they are applied
+
+// tarot cards that change the cards in hand
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Hash)]
+pub enum Tarot {
+    Strength,
+    Death,
+    TheLovers,
+    TheChariot,
+    TheEmpress,
+    TheHierophant,
+    Justice,
+    TheTower,
+    TheStar,
+    TheMoon,
+    TheSun,
+    TheWorld,
+}
+
+impl Tarot {
+    // list of all tarots, in the same order as the enum
+    pub const ALL: [Tarot; 12] = [
+        Tarot::Strength,
+        Tarot::Death,
+        Tarot::TheLovers,
+        Tarot::TheChariot,
+        Tarot::TheEmpress,
+        Tarot::TheHierophant,
+        Tarot::Justice,
+        Tarot::TheTower,
+        Tarot::TheStar,
+        Tarot::TheMoon,
+        Tarot::TheSun,
+        Tarot::TheWorld,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        #[rustfmt::skip]
+        let name = match self {
+            Tarot::Strength =>      "Strength",
+            Tarot::Death =>         "Death",
+            Tarot::TheLovers =>     "The Lovers",
+            Tarot::TheChariot =>    "The Chariot",
+            Tarot::TheEmpress =>    "The Empress",
+            Tarot::TheHierophant => "The Hierophant",
+            Tarot::Justice =>       "Justice",
+            Tarot::TheTower =>      "The Tower",
+            Tarot::TheStar =>       "The Star",
+            Tarot::TheMoon =>       "The Moon",
+            Tarot::TheSun =>        "The Sun",
+            Tarot::TheWorld =>      "The World",
+        };
+
+        name
+    }
+
+    // smallest and largest number of cards the tarot can target
+    pub fn target_range(&self) -> (usize, usize) {
+        match self {
+            Tarot::Death => (2, 2),
+            Tarot::Strength | Tarot::TheEmpress | Tarot::TheHierophant => (1, 2),
+            Tarot::TheStar | Tarot::TheMoon | Tarot::TheSun | Tarot::TheWorld => (1, 3),
+            Tarot::TheLovers | Tarot::TheChariot | Tarot::Justice | Tarot::TheTower => (1, 1),
+        }
+    }
+
+    // applies the tarot to the cards at the given indices of hand.
+    // indices must be distinct and in range. Death turns the first target into a copy..
+    // of the second, so the order of its targets matters
+    pub fn apply(&self, hand: &mut [Card], targets: &[usize]) -> Result<(), String> {
+        let (min_targets, max_targets) = self.target_range();
+        if targets.len() < min_targets || targets.len() > max_targets {
+            return Err(if max_targets == 1 {
+                format!("{self} needs exactly 1 target card")
+            } else if min_targets == max_targets {
+                format!("{self} needs exactly {min_targets} target cards")
+            } else {
+                format!("{self} needs {min_targets} to {max_targets} target cards")
+            });
+        }
+
+        match self {
+            // increase rank by one, King goes to Ace and Ace wraps round to 2
+            Tarot::Strength => {
+                for &index in targets {
+                    hand[index].rank = next_rank(hand[index].rank);
+                }
+            }
+            // first target becomes a copy of the second, e.g. the left card into the right
+            Tarot::Death => {
+                let right = hand[targets[1]];
+                let mut copy = Card::new(right.rank, right.suit, right.enhancement, right.edition);
+                copy.bonus_chips = right.bonus_chips;
+                hand[targets[0]] = copy;
+            }
+            Tarot::TheLovers => enhance(hand, targets, Enhancement::Wild),
+            Tarot::TheChariot => enhance(hand, targets, Enhancement::Steel),
+            Tarot::TheEmpress => enhance(hand, targets, Enhancement::Mult),
+            Tarot::TheHierophant => enhance(hand, targets, Enhancement::Bonus),
+            Tarot::Justice => enhance(hand, targets, Enhancement::Glass),
+            Tarot::TheTower => enhance(hand, targets, Enhancement::Stone),
+            Tarot::TheStar => change_suit(hand, targets, Suit::Diamonds),
+            Tarot::TheMoon => change_suit(hand, targets, Suit::Clubs),
+            Tarot::TheSun => change_suit(hand, targets, Suit::Hearts),
+            Tarot::TheWorld => change_suit(hand, targets, Suit::Spades),
+        }
+
+        Ok(())
+    }
+}
+
+fn enhance(hand: &mut [Card], targets: &[usize], enhancement: Enhancement) {
+    for &index in targets {
+        hand[index].enhancement = Some(enhancement);
+    }
+}
+
+fn change_suit(hand: &mut [Card], targets: &[usize], suit: Suit) {
+    for &index in targets {
+        hand[index].suit = suit;
+    }
+}
+
+fn next_rank(rank: Rank) -> Rank {
+    match rank {
+        Rank::Two => Rank::Three,
+        Rank::Three => Rank::Four,
+        Rank::Four => Rank::Five,
+        Rank::Five => Rank::Six,
+        Rank::Six => Rank::Seven,
+        Rank::Seven => Rank::Eight,
+        Rank::Eight => Rank::Nine,
+        Rank::Nine => Rank::Ten,
+        Rank::Ten => Rank::Jack,
+        Rank::Jack => Rank::Queen,
+        Rank::Queen => Rank::King,
+        Rank::King => Rank::Ace,
+        Rank::Ace => Rank::Two,
+    }
+}
+
+impl Display for Tarot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl Debug for Tarot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl FromStr for Tarot {
+    type Err = String;
+
+    // matches the name ignoring case, spaces and punctuation, like jokers, so "The Star",..
+    // "the star" and "TheStar" all parse
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let folded = fold_name(s);
+
+        Tarot::ALL
+            .into_iter()
+            .find(|tarot| fold_name(tarot.name()) == folded)
+            .ok_or_else(|| {
+                let names: Vec<&str> = Tarot::ALL.iter().map(|tarot| tarot.name()).collect();
+                format!(
+                    "Invalid Tarot: `{s}`. Expected one of: {}",
+                    names.join(", ")
+                )
+            })
+    }
+}
@@ -0,0 +1,118 @@
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::round::Round;
+use crate::scoring::scorer::{HandScore, score_round};
+use crate::tarot::tarot_card::Tarot;
+use std::fmt::{Display, Formatter};
+
+// File contains the what-if mode, which previews how a tarot changes a round's score.
+// cards are targeted by their 1-based position in the hand, counting played cards..
+// first and then cards held in hand
+
+// score of a round before and after a tarot is used
+pub struct WhatIfPreview {
+    pub tarot: Tarot,
+    // each targeted card before and after the tarot
+    pub changed_cards: Vec<(Card, Card)>,
+    pub before: HandScore,
+    pub after: HandScore,
+}
+
+// parses comma separated 1-based positions, e.g. "1,3", into sorted 0-based indices
+pub fn parse_targets(targets: &str, hand_size: usize) -> Result<Vec<usize>, String> {
+    let mut indices = vec![];
+
+    for target in targets.split(',') {
+        let position: usize = target.trim().parse().map_err(|_| {
+            format!(
+                "Invalid target `{}`: expected a card position",
+                target.trim()
+            )
+        })?;
+
+        if position == 0 || position > hand_size {
+            return Err(format!(
+                "Target {position} is out of range: the hand has {hand_size} cards"
+            ));
+        }
+        if indices.contains(&(position - 1)) {
+            return Err(format!("Target {position} is listed more than once"));
+        }
+        indices.push(position - 1);
+    }
+
+    indices.sort();
+    Ok(indices)
+}
+
+// returns a copy of round with the tarot applied to the targeted cards
+pub fn apply_tarot(round: &Round, tarot: Tarot, targets: &[usize]) -> Result<Round, String> {
+    let played_count = round.cards_played.len();
+
+    // tarots are used on the whole hand, so join played and held cards
+    let mut hand: Vec<Card> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .copied()
+        .collect();
+
+    if let Some(&target) = targets.iter().find(|&&target| target >= hand.len()) {
+        return Err(format!(
+            "Target {} is out of range: the hand has {} cards",
+            target + 1,
+            hand.len()
+        ));
+    }
+
+    tarot.apply(&mut hand, targets)?;
+
+    let mut updated_round = round.clone();
+    updated_round.cards_held_in_hand = hand.split_off(played_count);
+    updated_round.cards_played = hand;
+    Ok(updated_round)
+}
+
+// scores round before and after using the tarot on the targeted cards
+pub fn preview_tarot(
+    round: &Round,
+    tarot: Tarot,
+    targets: &[usize],
+) -> Result<WhatIfPreview, String> {
+    let updated_round = apply_tarot(round, tarot, targets)?;
+
+    let hand_before: Vec<&Card> = round
+        .cards_played
+        .iter()
+        .chain(&round.cards_held_in_hand)
+        .collect();
+    let hand_after: Vec<&Card> = updated_round
+        .cards_played
+        .iter()
+        .chain(&updated_round.cards_held_in_hand)
+        .collect();
+
+    let changed_cards = targets
+        .iter()
+        .map(|&index| (*hand_before[index], *hand_after[index]))
+        .collect();
+
+    Ok(WhatIfPreview {
+        tarot,
+        changed_cards,
+        before: score_round(round),
+        after: score_round(&updated_round),
+    })
+}
+
+impl Display for WhatIfPreview {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{}:", self.tarot)?;
+        for (before, after) in &self.changed_cards {
+            writeln!(f, "  {before} -> {after}")?;
+        }
+
+        writeln!(f, "Before: {} ({})", self.before.score, self.before.hand)?;
+        writeln!(f, "After:  {} ({})", self.after.score, self.after.hand)?;
+        write!(f, "Delta:  {:+}", self.after.score - self.before.score)
+    }
+}
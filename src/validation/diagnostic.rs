@@ -0,0 +1,71 @@
+use serde::Serialize;
+use std::error::Error;
+use std::fmt::{Debug, Display, Formatter};
+
+// File contains the diagnostics reported for invalid round files
+
+// a problem found in a round file, with the position it was found at.
+// line and column are 1-based
+#[derive(Serialize)]
+pub struct Diagnostic {
+    pub file: String,
+    pub line: usize,
+    pub column: usize,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+// every problem found while validating a round file
+pub struct ValidationError {
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl Diagnostic {
+    // message and suggestion without the position, for when the position is obvious..
+    // e.g. in the REPL
+    pub fn describe(&self) -> String {
+        match &self.suggestion {
+            Some(suggestion) => format!("{} (did you mean `{suggestion}`?)", self.message),
+            None => self.message.clone(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.file,
+            self.line,
+            self.column,
+            self.describe()
+        )
+    }
+}
+
+impl Debug for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let lines: Vec<String> = self
+            .diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.to_string())
+            .collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+impl Debug for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+impl Error for ValidationError {}
@@ -0,0 +1,4 @@
+pub mod diagnostic;
+pub mod round_file;
+pub mod round_flags;
+pub mod suggestion;
@@ -0,0 +1,436 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::notation::{parse_enhancement, parse_rank, parse_suit, split_card};
+use crate::scoring::card::playing_card::{BONUS_LABEL, Card, Edition, Enhancement};
+use crate::scoring::joker::joker_card::{Joker, JokerCard};
+use crate::scoring::round::{Round, RoundLocation};
+use crate::validation::diagnostic::{Diagnostic, ValidationError};
+use crate::validation::suggestion::closest_match;
+use serde_yaml::Value;
+use std::collections::HashMap;
+
+// File contains validation of round files. problems are reported with the line and..
+// column they were found at, along with the closest valid name for misspellings
+
+// top level fields a round file can have
+const ROUND_FIELDS: [&str; 5] = [
+    "cards_played",
+    "cards_held_in_hand",
+    "jokers",
+    "joker_slots",
+    "unique_cards",
+];
+
+const CARD_FIELDS: [&str; 2] = ["cards_played", "cards_held_in_hand"];
+
+// a problem found in a single card or joker entry.
+// offset is the byte offset of the problem within the entry
+pub struct EntryProblem {
+    pub offset: usize,
+    pub message: String,
+    pub suggestion: Option<String>,
+}
+
+// byte offsets of the fields and list entries of a round file, used to turn..
+// problems into line and column positions
+struct SourceMap<'a> {
+    source: &'a str,
+    fields: HashMap<String, usize>,
+    entries: HashMap<String, Vec<EntryPosition>>,
+}
+
+// where a list entry starts. entries written differently in the source, e.g. with..
+// escapes, can't be found, so offset is only roughly where they start
+#[derive(Clone, Copy)]
+struct EntryPosition {
+    offset: usize,
+    // set if the entry appears in the source as it was read
+    verbatim: bool,
+}
+
+// parses and validates a round file. file is the name used in diagnostics
+// malformed files are parse errors, well formed rounds that can't happen in game are..
+// invalid rounds
+pub fn validate_round_source(source: &str, file: &str) -> Result<Round, OrtalabError> {
+    let diagnostic_at = |offset: usize, message: String, suggestion: Option<String>| {
+        let (line, column) = line_and_column(source, offset);
+        Diagnostic {
+            file: file.to_string(),
+            line,
+            column,
+            message,
+            suggestion,
+        }
+    };
+
+    // syntax errors stop validation straight away
+    let value: Value = serde_yaml::from_str(source).map_err(|err| {
+        OrtalabError::Parse(ValidationError {
+            diagnostics: vec![yaml_diagnostic(&err, file)],
+        })
+    })?;
+
+    let Value::Mapping(mapping) = &value else {
+        return Err(OrtalabError::Parse(ValidationError {
+            diagnostics: vec![diagnostic_at(
+                0,
+                "Expected a round with cards_played, cards_held_in_hand and jokers".to_string(),
+                None,
+            )],
+        }));
+    };
+
+    let source_map = SourceMap::new(source, &value);
+    let mut diagnostics = vec![];
+
+    for key in mapping.keys() {
+        let key_name = key.as_str().unwrap_or_default();
+        if !ROUND_FIELDS.contains(&key_name) {
+            diagnostics.push(diagnostic_at(
+                source_map.field_offset(key_name),
+                format!("Unknown field `{key_name}`"),
+                closest_match(key_name, ROUND_FIELDS).map(str::to_string),
+            ));
+        }
+    }
+
+    for field in CARD_FIELDS.into_iter().chain(["jokers"]) {
+        let Some(Value::Sequence(entries)) = mapping.get(field) else {
+            continue;
+        };
+
+        for (index, entry) in entries.iter().enumerate() {
+            let offset = source_map.entry_offset(field, index);
+
+            let Some(entry) = entry.as_str() else {
+                diagnostics.push(diagnostic_at(
+                    offset,
+                    format!("Expected entry {} of {field} to be a string", index + 1),
+                    None,
+                ));
+                continue;
+            };
+
+            let problem = if field == "jokers" {
+                diagnose_joker(entry)
+            } else {
+                diagnose_card(entry)
+            };
+
+            if let Some(problem) = problem {
+                // offsets within the entry only line up with the source if the entry..
+                // was written as it was read
+                let problem_offset = if source_map.entry_is_verbatim(field, index) {
+                    offset + problem.offset
+                } else {
+                    offset
+                };
+                diagnostics.push(diagnostic_at(
+                    problem_offset,
+                    problem.message,
+                    problem.suggestion,
+                ));
+            }
+        }
+    }
+
+    if !diagnostics.is_empty() {
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        return Err(OrtalabError::Parse(ValidationError { diagnostics }));
+    }
+
+    // anything not caught above, such as a field of the wrong type, is left to serde
+    let mut round: Round = serde_yaml::from_str(source).map_err(|err| {
+        OrtalabError::Parse(ValidationError {
+            diagnostics: vec![yaml_diagnostic(&err, file)],
+        })
+    })?;
+
+    let mut diagnostics: Vec<Diagnostic> = round
+        .problems()
+        .into_iter()
+        .map(|problem| {
+            let offset = match problem.location {
+                RoundLocation::Field(field) => source_map.field_offset(field),
+                RoundLocation::PlayedCard(index) => source_map.entry_offset("cards_played", index),
+                RoundLocation::HeldCard(index) => {
+                    source_map.entry_offset("cards_held_in_hand", index)
+                }
+            };
+            diagnostic_at(offset, problem.message, None)
+        })
+        .collect();
+
+    if !diagnostics.is_empty() {
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.line, diagnostic.column));
+        return Err(OrtalabError::InvalidRound(ValidationError { diagnostics }));
+    }
+
+    round.assign_card_ids();
+    Ok(round)
+}
+
+// converts a serde_yaml error into a diagnostic at the position serde_yaml reports
+fn yaml_diagnostic(err: &serde_yaml::Error, file: &str) -> Diagnostic {
+    let (line, column) = err
+        .location()
+        .map(|location| (location.line(), location.column()))
+        .unwrap_or((1, 1));
+
+    // the position is already part of the diagnostic, so drop serde_yaml's copy of it
+    let mut message = err.to_string();
+    if let Some(start) = message.find(" at line ")
+        && let Some(column) = message[start..].find(" column ")
+    {
+        let digits_start = start + column + " column ".len();
+        let end = message[digits_start..]
+            .find(|character: char| !character.is_ascii_digit())
+            .map_or(message.len(), |position| digits_start + position);
+        message.replace_range(start..end, "");
+    }
+
+    Diagnostic {
+        file: file.to_string(),
+        line,
+        column,
+        message,
+        suggestion: None,
+    }
+}
+
+// 1-based line and column of a byte offset. columns count characters, not bytes. an..
+// offset inside a character counts as the start of that character
+fn line_and_column(source: &str, offset: usize) -> (usize, usize) {
+    let before = &source[..source.floor_char_boundary(offset)];
+    let line = before.matches('\n').count() + 1;
+    let line_start = before.rfind('\n').map_or(0, |position| position + 1);
+    let column = before[line_start..].chars().count() + 1;
+
+    (line, column)
+}
+
+impl<'a> SourceMap<'a> {
+    fn new(source: &'a str, value: &Value) -> Self {
+        // top level keys start at the beginning of a line
+        let mut fields = HashMap::new();
+        let mut line_start = 0;
+        for line in source.split_inclusive('\n') {
+            if let Some((key, _)) = line.split_once(':')
+                && !key.starts_with([' ', '\t', '-', '#'])
+            {
+                fields
+                    .entry(key.trim().trim_matches(['"', '\'']).to_string())
+                    .or_insert(line_start);
+            }
+            line_start += line.len();
+        }
+
+        // find each list entry in order, starting from its field
+        let mut entries = HashMap::new();
+        for field in CARD_FIELDS.into_iter().chain(["jokers"]) {
+            let Some(Value::Sequence(items)) = value.get(field) else {
+                continue;
+            };
+            let mut search_from = fields.get(field).copied().unwrap_or(0);
+            let mut offsets = vec![];
+
+            for item in items {
+                match item
+                    .as_str()
+                    .and_then(|text| Some((source[search_from..].find(text)?, text)))
+                {
+                    Some((position, text)) => {
+                        offsets.push(EntryPosition {
+                            offset: search_from + position,
+                            verbatim: true,
+                        });
+                        search_from += position + text.len();
+                    }
+                    None => offsets.push(EntryPosition {
+                        offset: search_from,
+                        verbatim: false,
+                    }),
+                }
+            }
+            entries.insert(field.to_string(), offsets);
+        }
+
+        SourceMap {
+            source,
+            fields,
+            entries,
+        }
+    }
+
+    fn field_offset(&self, field: &str) -> usize {
+        self.fields.get(field).copied().unwrap_or(0)
+    }
+
+    // offset of a list entry, falling back to its field, then to the end of the file
+    fn entry_offset(&self, field: &str, index: usize) -> usize {
+        self.entries
+            .get(field)
+            .and_then(|offsets| offsets.get(index))
+            .map(|position| position.offset)
+            .or_else(|| self.fields.get(field).copied())
+            .unwrap_or(self.source.len())
+    }
+
+    // checks whether a list entry appears in the source as it was read
+    fn entry_is_verbatim(&self, field: &str, index: usize) -> bool {
+        self.entries
+            .get(field)
+            .and_then(|offsets| offsets.get(index))
+            .is_some_and(|position| position.verbatim)
+    }
+}
+
+fn modifier_names() -> Vec<String> {
+    Enhancement::ALL
+        .iter()
+        .map(|enhancement| enhancement.to_string())
+        .chain(Edition::ALL.iter().map(|edition| edition.to_string()))
+        .collect()
+}
+
+// works out which part of a card entry is invalid
+pub fn diagnose_card(entry: &str) -> Option<EntryProblem> {
+    let parse_error = entry.parse::<Card>().err()?;
+    let problem = |offset: usize, message: String, suggestion: Option<String>| {
+        Some(EntryProblem {
+            offset,
+            message,
+            suggestion,
+        })
+    };
+
+    let Some(notation) = split_card(entry) else {
+        return problem(0, "Empty card".to_string(), None);
+    };
+
+    if parse_suit(notation.suit).is_none() {
+        return problem(
+            notation.suit_offset,
+            format!(
+                "Invalid suit `{}` in card `{entry}`, expected one of ♠ ♥ ♣ ♦ or S H C D",
+                notation.suit
+            ),
+            None,
+        );
+    }
+    if parse_rank(notation.rank).is_none() {
+        return problem(
+            notation.rank_offset,
+            format!(
+                "Invalid rank `{}` in card `{entry}`, expected 2-10, J, Q, K or A",
+                notation.rank
+            ),
+            None,
+        );
+    }
+
+    let mut modifiers = notation.modifiers;
+    if let Some(&(offset, last)) = modifiers.last()
+        && let Some(bonus) = last.strip_prefix('+')
+    {
+        if bonus.parse::<u32>().is_err() {
+            return problem(
+                offset,
+                format!("Invalid bonus chips `{last}` in card `{entry}`, expected e.g. `+25`"),
+                None,
+            );
+        }
+        modifiers.pop();
+        if modifiers
+            .last()
+            .is_some_and(|&(_, last)| last.eq_ignore_ascii_case(BONUS_LABEL))
+        {
+            modifiers.pop();
+        }
+    }
+
+    let modifier_names = modifier_names();
+    for &(offset, modifier) in &modifiers {
+        if modifier.starts_with('+') {
+            return problem(
+                offset,
+                format!("Bonus chips `{modifier}` in card `{entry}` must come last"),
+                None,
+            );
+        }
+        if parse_enhancement(modifier).is_none() && modifier.parse::<Edition>().is_err() {
+            return problem(
+                offset,
+                format!("Unknown enhancement or edition `{modifier}` in card `{entry}`"),
+                closest_match(modifier, modifier_names.iter().map(String::as_str))
+                    .map(str::to_string),
+            );
+        }
+    }
+
+    if let Some(&(offset, extra)) = modifiers.get(2) {
+        return problem(
+            offset,
+            format!(
+                "Too much data `{extra}` in card `{entry}`: a card has at most one enhancement \
+                 and one edition"
+            ),
+            None,
+        );
+    }
+
+    if let [(offset, first), (_, second)] = modifiers[..] {
+        return problem(
+            offset,
+            format!(
+                "Card `{entry}` should list its enhancement before its edition, \
+                 got `{first}` then `{second}`"
+            ),
+            None,
+        );
+    }
+
+    problem(notation.rank_offset, parse_error, None)
+}
+
+// works out which part of a joker entry is invalid
+pub fn diagnose_joker(entry: &str) -> Option<EntryProblem> {
+    entry.parse::<JokerCard>().err()?;
+    let joker_names = Joker::ALL.map(|joker| joker.name());
+    let offset = entry.len() - entry.trim_start().len();
+
+    // the whole entry may be a misspelt joker name
+    if let Some(joker) = closest_match(entry.trim(), joker_names) {
+        return Some(EntryProblem {
+            offset,
+            message: format!("Unknown joker `{}`", entry.trim()),
+            suggestion: Some(joker.to_string()),
+        });
+    }
+
+    // otherwise try the last word as a (possibly misspelt) edition
+    if let Some((name, edition)) = entry.trim().rsplit_once(' ') {
+        let edition_names = Edition::ALL.map(|edition| edition.name());
+        let joker = joker_names
+            .contains(&name.trim())
+            .then_some(name.trim())
+            .or_else(|| closest_match(name.trim(), joker_names));
+        let edition = edition_names
+            .contains(&edition)
+            .then_some(edition)
+            .or_else(|| closest_match(edition, edition_names));
+
+        if let (Some(joker), Some(edition)) = (joker, edition) {
+            return Some(EntryProblem {
+                offset,
+                message: format!("Unknown joker `{}`", entry.trim()),
+                suggestion: Some(format!("{joker} {edition}")),
+            });
+        }
+    }
+
+    Some(EntryProblem {
+        offset,
+        message: format!("Unknown joker `{}`", entry.trim()),
+        suggestion: None,
+    })
+}
@@ -0,0 +1,198 @@
+use crate::error::OrtalabError;
+use crate::scoring::card::playing_card::Card;
+use crate::scoring::joker::joker_card::JokerCard;
+use crate::scoring::round::{Round, RoundLocation};
+use crate::validation::diagnostic::{Diagnostic, ValidationError};
+use crate::validation::round_file::{EntryProblem, diagnose_card, diagnose_joker};
+use std::str::FromStr;
+
+// File contains validation of rounds given as command line flags instead of a round..
+// file, e.g. --play "AD KD QD" --hold "KS:Steel" --jokers "Splash,Blueprint".
+// diagnostics use the flag as the file name, with the column in the flag's value
+
+// one card or joker in a flag value. offset is the byte offset of the entry in the value
+struct FlagEntry {
+    offset: usize,
+    entry: String,
+}
+
+// a round flag and its value
+struct RoundFlag<'a> {
+    name: &'static str,
+    value: &'a str,
+}
+
+// splits a card list on commas if it has any, otherwise on whitespace, so both..
+// "AD KD:Glass" and "Ace of Diamonds, King of Diamonds Glass" work
+fn split_cards(value: &str) -> Vec<FlagEntry> {
+    if value.contains(',') {
+        split_entries(value, |character| character == ',')
+    } else {
+        split_entries(value, char::is_whitespace)
+    }
+}
+
+// splits value into trimmed, non-empty entries. modifiers may be attached with `:`,..
+// e.g. "KS:Steel:Foil", which is swapped for a space. both are a single byte, so..
+// offsets into the entry still line up with the value
+fn split_entries(value: &str, is_separator: impl Fn(char) -> bool) -> Vec<FlagEntry> {
+    let mut entries = vec![];
+    let mut entry_start = 0;
+
+    for (offset, character) in value.char_indices().chain([(value.len(), ',')]) {
+        if offset < value.len() && !is_separator(character) {
+            continue;
+        }
+
+        let raw = &value[entry_start..offset];
+        let trimmed = raw.trim_start();
+        if !trimmed.trim_end().is_empty() {
+            entries.push(FlagEntry {
+                offset: entry_start + raw.len() - trimmed.len(),
+                entry: trimmed.trim_end().replace(':', " "),
+            });
+        }
+        entry_start = offset + character.len_utf8();
+    }
+
+    entries
+}
+
+fn flag_diagnostic(
+    flag: &RoundFlag,
+    offset: usize,
+    message: String,
+    suggestion: Option<String>,
+) -> Diagnostic {
+    Diagnostic {
+        file: flag.name.to_string(),
+        line: 1,
+        column: flag.value[..offset.min(flag.value.len())].chars().count() + 1,
+        message,
+        suggestion,
+    }
+}
+
+// parses entries that have already been diagnosed
+fn parse_entries<T: FromStr<Err = String>>(entries: &[FlagEntry]) -> Vec<T> {
+    entries
+        .iter()
+        .map(|flag_entry| flag_entry.entry.parse().expect("entry was validated"))
+        .collect()
+}
+
+fn diagnose_entries(
+    flag: &RoundFlag,
+    entries: &[FlagEntry],
+    diagnose_entry: fn(&str) -> Option<EntryProblem>,
+) -> Vec<Diagnostic> {
+    entries
+        .iter()
+        .filter_map(|flag_entry| {
+            let problem = diagnose_entry(&flag_entry.entry)?;
+            Some(flag_diagnostic(
+                flag,
+                flag_entry.offset + problem.offset,
+                problem.message,
+                problem.suggestion,
+            ))
+        })
+        .collect()
+}
+
+// parses a list of cards written the same way as --play. name is used in diagnostics
+pub fn parse_card_list(name: &'static str, value: &str) -> Result<Vec<Card>, ValidationError> {
+    let flag = RoundFlag { name, value };
+    let entries = split_cards(value);
+    let diagnostics = diagnose_entries(&flag, &entries, diagnose_card);
+
+    if !diagnostics.is_empty() {
+        return Err(ValidationError { diagnostics });
+    }
+    Ok(parse_entries(&entries))
+}
+
+// parses a comma separated list of jokers written the same way as --jokers
+pub fn parse_joker_list(
+    name: &'static str,
+    value: &str,
+) -> Result<Vec<JokerCard>, ValidationError> {
+    let flag = RoundFlag { name, value };
+    let entries = split_entries(value, |character| character == ',');
+    let diagnostics = diagnose_entries(&flag, &entries, diagnose_joker);
+
+    if !diagnostics.is_empty() {
+        return Err(ValidationError { diagnostics });
+    }
+    Ok(parse_entries(&entries))
+}
+
+// builds and validates a round from the --play, --hold and --jokers flags. bad cards or..
+// jokers are parse errors, rounds that can't happen in game are invalid rounds
+pub fn validate_round_flags(
+    play: &str,
+    hold: Option<&str>,
+    jokers: Option<&str>,
+) -> Result<Round, OrtalabError> {
+    let play_flag = RoundFlag {
+        name: "--play",
+        value: play,
+    };
+    let hold_flag = RoundFlag {
+        name: "--hold",
+        value: hold.unwrap_or_default(),
+    };
+    let jokers_flag = RoundFlag {
+        name: "--jokers",
+        value: jokers.unwrap_or_default(),
+    };
+
+    let play_entries = split_cards(play_flag.value);
+    let hold_entries = split_cards(hold_flag.value);
+    let joker_entries = split_entries(jokers_flag.value, |character| character == ',');
+
+    let mut diagnostics = diagnose_entries(&play_flag, &play_entries, diagnose_card);
+    diagnostics.extend(diagnose_entries(&hold_flag, &hold_entries, diagnose_card));
+    diagnostics.extend(diagnose_entries(
+        &jokers_flag,
+        &joker_entries,
+        diagnose_joker,
+    ));
+
+    if !diagnostics.is_empty() {
+        return Err(OrtalabError::Parse(ValidationError { diagnostics }));
+    }
+
+    let mut round = Round {
+        cards_played: parse_entries(&play_entries),
+        cards_held_in_hand: parse_entries(&hold_entries),
+        jokers: parse_entries(&joker_entries),
+        ..Round::default()
+    };
+
+    let diagnostics: Vec<Diagnostic> = round
+        .problems()
+        .into_iter()
+        .map(|problem| {
+            let (flag, offset) = match problem.location {
+                RoundLocation::PlayedCard(index) => (
+                    &play_flag,
+                    play_entries.get(index).map_or(0, |entry| entry.offset),
+                ),
+                RoundLocation::HeldCard(index) => (
+                    &hold_flag,
+                    hold_entries.get(index).map_or(0, |entry| entry.offset),
+                ),
+                RoundLocation::Field(_) => (&play_flag, 0),
+            };
+            flag_diagnostic(flag, offset, problem.message, None)
+        })
+        .collect();
+
+    if !diagnostics.is_empty() {
+        return Err(OrtalabError::InvalidRound(ValidationError { diagnostics }));
+    }
+
+    round.assign_card_ids();
+    Ok(round)
+}
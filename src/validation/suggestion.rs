@@ -0,0 +1,72 @@
+// File contains helpers for suggesting the valid name closest to a misspelt one, and..
+// for accepting a close enough misspelling as that name
+
+// number of single character insertions, deletions and substitutions needed to..
+// turn a into b, ignoring case
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+
+    // distances from the previous row of the table, starting with the empty prefix of a
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, a_char) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            let deletion = previous[j + 1] + 1;
+            let insertion = current[j] + 1;
+            current.push(substitution.min(deletion).min(insertion));
+        }
+
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+// returns the candidate closest to input, if it is close enough to be a likely typo
+pub fn closest_match<'a>(
+    input: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<&'a str> {
+    // allow roughly one mistake for every three characters, and at least two
+    let max_distance = (input.chars().count() / 3).max(2);
+
+    candidates
+        .into_iter()
+        .map(|candidate| (edit_distance(input, candidate), candidate))
+        .filter(|&(distance, _)| distance <= max_distance)
+        .min_by_key(|&(distance, _)| distance)
+        .map(|(_, candidate)| candidate)
+}
+
+// returns the value named by the candidate closest to input, if input is close enough..
+// to take as a typo of it and no candidate naming another value is as close. allows one..
+// mistake for every four characters, so inputs under four characters must match exactly
+pub fn accept_typo<T: Copy + PartialEq>(
+    input: &str,
+    candidates: impl IntoIterator<Item = (impl AsRef<str>, T)>,
+) -> Option<T> {
+    let max_distance = input.chars().count() / 4;
+    let mut closest: Option<(usize, T)> = None;
+    let mut tied = false;
+
+    for (candidate, value) in candidates {
+        let distance = edit_distance(input, candidate.as_ref());
+        if distance > max_distance {
+            continue;
+        }
+        match closest {
+            Some((best, best_value)) if distance == best => tied |= value != best_value,
+            Some((best, _)) if distance > best => {}
+            _ => {
+                closest = Some((distance, value));
+                tied = false;
+            }
+        }
+    }
+
+    closest.filter(|_| !tied).map(|(_, value)| value)
+}
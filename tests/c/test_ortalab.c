@@ -0,0 +1,50 @@
+/*
+ * Scores a few rounds through the C API and checks the JSON results.
+ * Run by tests/c_api.rs, or by hand after `cargo build`:
+ *
+ *   cc tests/c/test_ortalab.c -Iinclude -Ltarget/debug -lortalab -o test_ortalab
+ *   LD_LIBRARY_PATH=target/debug ./test_ortalab
+ */
+
+#include <stdio.h>
+#include <string.h>
+
+#include "ortalab.h"
+
+static int failures = 0;
+
+static void expect_contains(const char *round, const char *expected) {
+    char *result = ortalab_score(round);
+
+    if (result == NULL) {
+        printf("FAIL: NULL result for %s\n", round);
+        failures++;
+        return;
+    }
+    if (strstr(result, expected) == NULL) {
+        printf("FAIL: expected `%s` in %s\n", expected, result);
+        failures++;
+    } else {
+        printf("ok: %s\n", result);
+    }
+
+    ortalab_free_string(result);
+}
+
+int main(void) {
+    expect_contains(
+        "{\"cards_played\": [\"A♦\", \"K♦\", \"Q♦\", \"J♦\", \"10♦\"], \"jokers\": [\"Blueprint\", \"Splash\"]}",
+        "\"score\":1208.0");
+    expect_contains("cards_played:\n  - AD\n  - AS\njokers:\n  - Joker\n", "\"hand\":\"Pair\"");
+    expect_contains("cards_played: [A♦ Glas]", "\"kind\":\"parse\"");
+    expect_contains("cards_played: []", "\"kind\":\"invalid_round\"");
+    expect_contains(NULL, "\"kind\":\"invalid_argument\"");
+
+    ortalab_free_string(NULL);
+
+    if (failures > 0) {
+        printf("%d failure(s)\n", failures);
+        return 1;
+    }
+    return 0;
+}
@@ -0,0 +1,40 @@
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+// builds tests/c/test_ortalab.c against the shared library and runs it.
+// skipped when no C compiler is installed
+#[test]
+fn c_test_program_links_and_passes() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    // cargo test builds the shared library into the same deps directory as this test
+    let test_exe = std::env::current_exe().expect("test executable path");
+    let library_dir: PathBuf = test_exe.parent().expect("deps directory").to_path_buf();
+    let program = PathBuf::from(env!("CARGO_TARGET_TMPDIR")).join("test_ortalab");
+
+    let compile = Command::new("cc")
+        .arg(manifest_dir.join("tests/c/test_ortalab.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg("-L")
+        .arg(&library_dir)
+        .arg("-lortalab")
+        .arg("-o")
+        .arg(&program)
+        .status();
+    let Ok(compile) = compile else {
+        eprintln!("skipping: no C compiler found");
+        return;
+    };
+    assert!(compile.success(), "compiling the C test program failed");
+
+    let output = Command::new(&program)
+        .env("LD_LIBRARY_PATH", &library_dir)
+        .env("DYLD_LIBRARY_PATH", &library_dir)
+        .output()
+        .expect("run the C test program");
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    assert!(
+        output.status.success(),
+        "the C test program reported failures"
+    );
+}
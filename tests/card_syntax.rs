@@ -0,0 +1,56 @@
+use ortalab::scoring::card::playing_card::{Card, Enhancement};
+use ortalab::scoring::scorer::calculate_score;
+use ortalab::validation::round_flags::validate_round_flags;
+
+// checks how permanent bonus chips are written on cards and how they score
+
+fn score(play: &str, jokers: Option<&str>) -> f64 {
+    let round = validate_round_flags(play, None, jokers).expect("valid round");
+    let (chips, mult) = calculate_score(round);
+    (chips * mult).floor()
+}
+
+#[test]
+fn bonus_label_belongs_to_the_chips() {
+    let card: Card = "K♦ Bonus +25".parse().expect("valid card");
+    assert_eq!(card.enhancement, None);
+    assert_eq!(card.bonus_chips, 25);
+
+    let card: Card = "K♦ Bonus Bonus +25".parse().expect("valid card");
+    assert_eq!(card.enhancement, Some(Enhancement::Bonus));
+    assert_eq!(card.bonus_chips, 25);
+
+    // without chips, Bonus is still the enhancement
+    let card: Card = "K♦ Bonus".parse().expect("valid card");
+    assert_eq!(card.enhancement, Some(Enhancement::Bonus));
+    assert_eq!(card.bonus_chips, 0);
+}
+
+#[test]
+fn bonus_chips_read_back_the_same() {
+    for written in ["K♦ Bonus +25", "K♦ +25", "K♦ Bonus Foil Bonus +25"] {
+        let card: Card = written.parse().expect("valid card");
+        let read_back: Card = card.to_string().parse().expect("valid card");
+        assert_eq!(read_back, card, "{written}");
+    }
+}
+
+#[test]
+fn bonus_chips_are_added_when_the_card_scores() {
+    // High Card 5 chips, King 10, 25 bonus chips
+    assert_eq!(score("KD:Bonus:+25", None), 40.0);
+    // plus 30 from the Bonus enhancement
+    assert_eq!(score("KD:Bonus:Bonus:+25", None), 70.0);
+}
+
+#[test]
+fn bonus_chips_that_do_not_fit_are_rejected() {
+    assert!("K♦ +4294967295".parse::<Card>().is_ok());
+    assert!("K♦ +4294967296".parse::<Card>().is_err());
+}
+
+#[test]
+fn hiker_stops_growing_a_full_bonus() {
+    let score = score("KD:+4294967295", Some("Hiker"));
+    assert_eq!(score, 5.0 + 10.0 + f64::from(u32::MAX));
+}
@@ -0,0 +1,23 @@
+use ortalab::scoring::scorer::calculate_score;
+use ortalab::validation::round_flags::validate_round_flags;
+
+// checks that copy jokers are resolved the same however long the joker row is
+
+fn score(play: &str, jokers: &str) -> f64 {
+    let round = validate_round_flags(play, None, Some(jokers)).expect("valid round");
+    let (chips, mult) = calculate_score(round);
+    (chips * mult).floor()
+}
+
+#[test]
+fn every_blueprint_in_a_long_chain_copies_the_joker_at_its_end() {
+    let jokers = format!("{}Joker", "Blueprint,".repeat(20));
+    // Pair 10 chips and two Aces 22, x2 mult and +4 from Joker and each of its 20 copies
+    assert_eq!(score("AS AH", &jokers), 32.0 * 86.0);
+}
+
+#[test]
+fn copy_jokers_that_copy_each_other_copy_nothing() {
+    assert_eq!(score("AS AH", "Brainstorm"), 64.0);
+    assert_eq!(score("AS AH", "Brainstorm,Blueprint,Brainstorm"), 64.0);
+}
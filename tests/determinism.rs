@@ -0,0 +1,186 @@
+use ortalab::scoring::joker::joker_wrappers::JokerEffectFlags;
+use ortalab::scoring::report::report_score;
+use ortalab::scoring::round::Round;
+use ortalab::scoring::scorer::find_best_hand;
+use ortalab::search::best_play::find_best_play_with;
+use ortalab::search::discard::DiscardSearch;
+use ortalab::search::parallel::SearchOptions;
+use ortalab::simulate::deck::{parse_deck, remaining_deck};
+use ortalab::simulate::simulation::{PlayPolicy, Simulation};
+use ortalab::validation::round_flags::{parse_card_list, validate_round_flags};
+use std::thread;
+
+// checks that scoring gives the same result every time a round is scored, that ties..
+// between groups of cards are broken by the documented order, and that searches and..
+// simulations don't depend on the number of workers
+
+const RUNS: usize = 500;
+
+// rounds where several groups of cards make the best hand, or jokers depend on which..
+// cards are picked
+const TIED_ROUNDS: [(&str, &str, &str); 5] = [
+    ("5H 2H 3H 4H 5D", "", "Four Fingers,Photograph"),
+    ("KS KH QD QC 9S", "9H", "Mad Joker,Baron"),
+    (
+        "AS 2D:Wild 3H 4C:Wild 7S",
+        "",
+        "Four Fingers,Smeared Joker,Flower Pot",
+    ),
+    (
+        "10H 10D 10S 10C 10H",
+        "KS KS",
+        "Raised Fist,Blueprint,Brainstorm",
+    ),
+    ("2C 4C 6C 8C 9D", "", "Shortcut,Four Fingers,Crafty Joker"),
+];
+
+fn tied_round(&(play, hold, jokers): &(&str, &str, &str)) -> Round {
+    validate_round_flags(play, Some(hold), Some(jokers)).expect("valid round")
+}
+
+// the full JSON report of a round, with every scoring step
+fn scored(round: &Round) -> String {
+    serde_json::to_string(&report_score(round, true)).expect("serialisable report")
+}
+
+fn best_hand_cards(cards: &str, flags: &JokerEffectFlags) -> Vec<String> {
+    let cards = parse_card_list("cards", cards).expect("valid cards");
+    let (_, hand_cards) = find_best_hand(&cards, flags);
+    hand_cards.iter().map(ToString::to_string).collect()
+}
+
+#[test]
+fn rescoring_a_round_gives_identical_output() {
+    for flags in &TIED_ROUNDS {
+        let expected = scored(&tied_round(flags));
+        for _ in 0..RUNS {
+            assert_eq!(scored(&tied_round(flags)), expected, "{flags:?}");
+        }
+    }
+}
+
+#[test]
+fn scoring_on_several_threads_gives_identical_output() {
+    let expected: Vec<String> = TIED_ROUNDS
+        .iter()
+        .map(|flags| scored(&tied_round(flags)))
+        .collect();
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for _ in 0..RUNS / 4 {
+                    for (flags, expected) in TIED_ROUNDS.iter().zip(&expected) {
+                        assert_eq!(&scored(&tied_round(flags)), expected, "{flags:?}");
+                    }
+                }
+            });
+        }
+    });
+}
+
+#[test]
+fn best_play_does_not_depend_on_worker_count() {
+    let hand = parse_card_list("hand", "KS KH 9D 9C 4S 4H 2D 2C").expect("valid cards");
+    let jokers = validate_round_flags("AS", None, Some("Mad Joker,Baron,Raised Fist"))
+        .expect("valid jokers")
+        .jokers;
+
+    let describe = |workers| {
+        let play = find_best_play_with(&hand, &jokers, &SearchOptions::with_workers(workers))
+            .expect("a play");
+        (scored(&play.round), play.score.score)
+    };
+
+    let expected = describe(1);
+    for workers in [2, 3, 8] {
+        for _ in 0..10 {
+            assert_eq!(describe(workers), expected, "{workers} workers");
+        }
+    }
+}
+
+#[test]
+fn simulation_depends_only_on_the_seed() {
+    let simulation = |seed| Simulation {
+        deck: parse_deck("--deck", "standard").expect("preset deck"),
+        hand_size: 8,
+        jokers: validate_round_flags("AS", None, Some("Jolly Joker,Baron"))
+            .expect("valid jokers")
+            .jokers,
+        policy: PlayPolicy::BestScore,
+        hands: 100,
+        seed,
+    };
+    let report = |seed, workers| {
+        let report = simulation(seed).run(&SearchOptions::with_workers(workers));
+        serde_json::to_string(&report).expect("serialisable report")
+    };
+
+    let expected = report(7, 1);
+    for workers in [1, 2, 5] {
+        assert_eq!(report(7, workers), expected, "{workers} workers");
+    }
+    assert_ne!(report(8, 1), expected, "another seed draws other hands");
+}
+
+#[test]
+fn discard_advice_depends_only_on_the_seed() {
+    let hand = parse_card_list("--hand", "AS KS 7H 7D 2C").expect("valid cards");
+    let search = DiscardSearch {
+        deck: remaining_deck(
+            &parse_deck("--deck", "standard").expect("preset deck"),
+            &hand,
+        ),
+        hand,
+        jokers: validate_round_flags("AS", None, Some("Jolly Joker"))
+            .expect("valid jokers")
+            .jokers,
+        discards_left: 1,
+        target: Some(100.0),
+        exact_limit: 50,
+        samples: 20,
+        seed: 3,
+    };
+    let advice = |workers| {
+        let advice = search.run(&SearchOptions::with_workers(workers));
+        serde_json::to_string(&advice).expect("serialisable advice")
+    };
+
+    let expected = advice(1);
+    for workers in [2, 5] {
+        assert_eq!(advice(workers), expected, "{workers} workers");
+    }
+}
+
+#[test]
+fn ties_prefer_more_chips_then_higher_ranks_then_played_order() {
+    let no_jokers = JokerEffectFlags::default();
+
+    // the two highest pairs score the most chips
+    assert_eq!(
+        best_hand_cards("2S 2H KS KH AS AH", &no_jokers),
+        ["K♠", "K♥", "A♠", "A♥"]
+    );
+    // a Jack and a King score the same chips, so the higher rank wins
+    assert_eq!(best_hand_cards("JS KS", &no_jokers), ["K♠"]);
+    // modifiers only break ties between the same ranks, so they never beat a higher rank
+    assert_eq!(best_hand_cards("KS JS:Foil", &no_jokers), ["K♠"]);
+    assert_eq!(best_hand_cards("KS 10S:Glass", &no_jokers), ["K♠"]);
+    assert_eq!(
+        best_hand_cards("KC:Bonus 10C:Foil", &no_jokers),
+        ["K♣ Bonus"]
+    );
+    // identical ranks go to the cards played first
+    assert_eq!(best_hand_cards("7D 7C 7H", &no_jokers), ["7♦", "7♣", "7♥"]);
+    assert_eq!(
+        best_hand_cards("9S 9H 9D 9C 9S 9H", &no_jokers),
+        ["9♠", "9♥", "9♦", "9♣", "9♠"]
+    );
+
+    // Ace 3 4 9 Jack of spades score more chips than Ace 2 4 9 Jack
+    assert_eq!(
+        best_hand_cards("AS 2S 3S 4S JS 9S", &no_jokers),
+        ["A♠", "3♠", "4♠", "J♠", "9♠"]
+    );
+}
@@ -0,0 +1,35 @@
+use ortalab::search::discard::DiscardSearch;
+use ortalab::search::parallel::SearchOptions;
+use ortalab::validation::round_flags::parse_card_list;
+
+// checks that the discard advisor plans for the discards left after the first
+
+fn best_discard(discards_left: usize) -> Vec<String> {
+    let search = DiscardSearch {
+        hand: parse_card_list("--hand", "AS KS 7H 7D 2C").expect("valid cards"),
+        deck: parse_card_list("--deck", "7S 7C QH QD 2H 3D 9C").expect("valid cards"),
+        jokers: vec![],
+        discards_left,
+        target: None,
+        exact_limit: 1000,
+        samples: 200,
+        seed: 0,
+    };
+    search.validate().expect("something to advise on");
+
+    let advice = search.run(&SearchOptions::with_workers(1));
+    advice.options[0]
+        .cards
+        .iter()
+        .map(ToString::to_string)
+        .collect()
+}
+
+#[test]
+fn more_discards_left_change_the_advice() {
+    // with one discard, keeping only the pair of 7s scores the most on average
+    assert_eq!(best_discard(1), ["A♠", "K♠", "2♣"]);
+    // with a second discard played out after the draw, keeping the Ace as well scores..
+    // more
+    assert_eq!(best_discard(2), ["K♠", "2♣"]);
+}
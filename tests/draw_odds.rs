@@ -0,0 +1,96 @@
+use ortalab::scoring::card::hand_eval::{HANDS, HandAnalysis};
+use ortalab::scoring::card::playing_card::{Card, Enhancement};
+use ortalab::scoring::joker::joker_card::JokerCard;
+use ortalab::scoring::joker::joker_util::set_joker_effects;
+use ortalab::search::best_play::index_combinations;
+use ortalab::search::parallel::SearchOptions;
+use ortalab::simulate::odds::DrawOdds;
+use ortalib::{Rank, Suit};
+use proptest::prelude::*;
+
+// compares the grouped draw counting behind the odds subcommand with analysing every..
+// draw one at a time on small decks, where repeated and Wild cards are common
+
+// few ranks, so repeated cards are common, but enough for straights including Ace high
+const RANKS: [Rank; 6] = [
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+const JOKERS: [&str; 3] = ["Four Fingers", "Shortcut", "Smeared Joker"];
+
+fn card() -> impl Strategy<Value = Card> {
+    (
+        0..RANKS.len(),
+        prop_oneof![Just(Suit::Spades), Just(Suit::Hearts), Just(Suit::Diamonds)],
+        prop_oneof![
+            6 => Just(None),
+            2 => Just(Some(Enhancement::Wild)),
+            1 => Just(Some(Enhancement::Stone)),
+        ],
+    )
+        .prop_map(|(rank, suit, enhancement)| Card::new(RANKS[rank], suit, enhancement, None))
+}
+
+fn jokers() -> impl Strategy<Value = Vec<JokerCard>> {
+    any::<[bool; 3]>().prop_map(|enabled| {
+        JOKERS
+            .iter()
+            .zip(enabled)
+            .filter(|&(_, enabled)| enabled)
+            .map(|(name, _)| name.parse().unwrap())
+            .collect()
+    })
+}
+
+// number of draws where held and drawn cards contain each hand, in HANDS order
+fn brute_force_counts(odds: &DrawOdds) -> Vec<u64> {
+    let joker_effects = set_joker_effects(&odds.jokers);
+    let mut counts = vec![0; HANDS.len()];
+
+    for draw in index_combinations(odds.deck.len(), odds.draws) {
+        let mut cards = odds.held.clone();
+        cards.extend(draw.iter().map(|&index| odds.deck[index]));
+        let analysis = HandAnalysis::new(&cards, &joker_effects);
+
+        for (count, hand) in counts.iter_mut().zip(HANDS) {
+            if analysis.contains(hand) {
+                *count += 1;
+            }
+        }
+    }
+
+    counts
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(512))]
+
+    #[test]
+    fn odds_match_analysing_every_draw(
+        held in prop::collection::vec(card(), 0..=4),
+        deck in prop::collection::vec(card(), 1..=12),
+        draws in 1..=5usize,
+        jokers in jokers(),
+    ) {
+        let odds = DrawOdds {
+            draws: draws.min(deck.len()),
+            held,
+            deck,
+            jokers,
+        };
+        let report = odds.run(&SearchOptions::with_workers(2));
+
+        // the report lists the best hand first
+        let counts: Vec<u64> = report.hands.iter().rev().map(|odds| odds.makes_ways).collect();
+        prop_assert_eq!(counts, brute_force_counts(&odds));
+        prop_assert_eq!(
+            report.total_draws as usize,
+            index_combinations(odds.deck.len(), odds.draws).len()
+        );
+    }
+}
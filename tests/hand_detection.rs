@@ -0,0 +1,206 @@
+mod oracle;
+
+use oracle::{oracle_best_hand, oracle_contained_hands};
+use ortalab::scoring::card::hand_eval::HandAnalysis;
+use ortalab::scoring::card::playing_card::{Card, CardId, Edition, Enhancement};
+use ortalab::scoring::joker::joker_wrappers::JokerEffectFlags;
+use ortalab::scoring::scorer::{calculate_score, find_best_hand};
+use ortalab::simulate::odds::HandCounts;
+use ortalab::validation::round_flags::validate_round_flags;
+use ortalib::{PokerHand, Rank, Suit};
+use proptest::prelude::*;
+use std::ops::RangeInclusive;
+
+// compares find_best_hand and HandAnalysis with the reference evaluator on random..
+// played hands and random passive joker effects, and the count based hand check used..
+// for draw odds with HandAnalysis on larger hands. also checks that a tie between groups..
+// of cards scores the same whichever was played first
+
+const HANDS: [PokerHand; 12] = [
+    PokerHand::HighCard,
+    PokerHand::Pair,
+    PokerHand::TwoPair,
+    PokerHand::ThreeOfAKind,
+    PokerHand::Straight,
+    PokerHand::Flush,
+    PokerHand::FullHouse,
+    PokerHand::FourOfAKind,
+    PokerHand::StraightFlush,
+    PokerHand::FiveOfAKind,
+    PokerHand::FlushHouse,
+    PokerHand::FlushFive,
+];
+
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+fn suit() -> impl Strategy<Value = Suit> {
+    prop_oneof![
+        Just(Suit::Spades),
+        Just(Suit::Hearts),
+        Just(Suit::Clubs),
+        Just(Suit::Diamonds),
+    ]
+}
+
+fn enhancement() -> impl Strategy<Value = Option<Enhancement>> {
+    prop_oneof![
+        3 => Just(None),
+        2 => Just(Some(Enhancement::Wild)),
+        1 => Just(Some(Enhancement::Glass)),
+        1 => Just(Some(Enhancement::Stone)),
+        1 => Just(Some(Enhancement::Bonus)),
+        1 => Just(Some(Enhancement::Mult)),
+    ]
+}
+
+fn edition() -> impl Strategy<Value = Option<Edition>> {
+    prop_oneof![
+        4 => Just(None),
+        1 => Just(Some(Edition::Foil)),
+        1 => Just(Some(Edition::Holographic)),
+        1 => Just(Some(Edition::Polychrome)),
+    ]
+}
+
+fn bonus_chips() -> impl Strategy<Value = u32> {
+    prop_oneof![3 => Just(0), 1 => 1..60u32]
+}
+
+// ranks are drawn from a window of six, wrapping past Ace, so pairs, straights and..
+// near straights like Q K A 2 3 come up often
+fn cards(count: RangeInclusive<usize>) -> impl Strategy<Value = Vec<Card>> {
+    (
+        0..RANKS.len(),
+        prop::collection::vec(
+            (0..6usize, suit(), enhancement(), edition(), bonus_chips()),
+            count,
+        ),
+    )
+        .prop_map(|(base, entries)| {
+            entries
+                .into_iter()
+                .enumerate()
+                .map(
+                    |(index, (offset, suit, enhancement, edition, bonus_chips))| {
+                        let rank = RANKS[(base + offset) % RANKS.len()];
+                        let mut card = Card::new(rank, suit, enhancement, edition);
+                        card.bonus_chips = bonus_chips;
+                        // ids keep duplicate cards apart, so the exact cards can be compared
+                        card.id = CardId::Played(index);
+                        card
+                    },
+                )
+                .collect()
+        })
+}
+
+fn played_cards() -> impl Strategy<Value = Vec<Card>> {
+    cards(1..=5)
+}
+
+fn joker_effects() -> impl Strategy<Value = JokerEffectFlags> {
+    any::<[bool; 5]>().prop_map(
+        |[four_fingers, shortcut, pareidolia, splash, smeared_joker]| JokerEffectFlags {
+            four_fingers,
+            shortcut,
+            pareidolia,
+            splash,
+            smeared_joker,
+        },
+    )
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(4096))]
+
+    #[test]
+    fn best_hand_matches_oracle(cards in played_cards(), flags in joker_effects()) {
+        let names: Vec<String> = cards.iter().map(ToString::to_string).collect();
+        let (hand, hand_cards) = find_best_hand(&cards, &flags);
+        let (expected_hand, expected_cards) = oracle_best_hand(&cards, &flags);
+
+        prop_assert!(
+            hand == expected_hand && hand_cards == expected_cards,
+            "{} with four fingers {}, shortcut {}, smeared {}: found {} {:?}, expected {} {:?}",
+            names.join(" "),
+            flags.four_fingers,
+            flags.shortcut,
+            flags.smeared_joker,
+            hand,
+            hand_cards.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            expected_hand,
+            expected_cards.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn contained_hands_match_oracle(cards in played_cards(), flags in joker_effects()) {
+        let names: Vec<String> = cards.iter().map(ToString::to_string).collect();
+        let analysis = HandAnalysis::new(&cards, &flags);
+        let contained: Vec<PokerHand> = HANDS
+            .into_iter()
+            .filter(|&hand| analysis.contains(hand))
+            .collect();
+        let expected = oracle_contained_hands(&cards, &flags);
+
+        prop_assert!(
+            contained == expected,
+            "{} with four fingers {}, shortcut {}, smeared {}: contains {:?}, expected {:?}",
+            names.join(" "),
+            flags.four_fingers,
+            flags.shortcut,
+            flags.smeared_joker,
+            contained.iter().map(ToString::to_string).collect::<Vec<_>>(),
+            expected.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn draw_counts_match_hand_analysis(cards in cards(1..=10), flags in joker_effects()) {
+        let names: Vec<String> = cards.iter().map(ToString::to_string).collect();
+        let analysis = HandAnalysis::new(&cards, &flags);
+        let counts = HandCounts::from_cards(&cards, &flags);
+
+        for hand in HANDS {
+            prop_assert!(
+                counts.contains(hand, &flags) == analysis.contains(hand),
+                "{} with four fingers {}, shortcut {}, smeared {}: {} counts {}, analysis {}",
+                names.join(" "),
+                flags.four_fingers,
+                flags.shortcut,
+                flags.smeared_joker,
+                hand,
+                counts.contains(hand, &flags),
+                analysis.contains(hand),
+            );
+        }
+    }
+}
+
+fn score(play: &str, jokers: &str) -> f64 {
+    let round = validate_round_flags(play, None, Some(jokers)).expect("valid round");
+    let (chips, mult) = calculate_score(round);
+    (chips * mult).floor()
+}
+
+#[test]
+fn ties_prefer_the_cards_that_add_more_to_the_score() {
+    // with Four Fingers either Queen completes the straight flush, and the Foil one..
+    // scores 50 more chips wherever it was played
+    assert_eq!(score("9H 10H JH QH QH:Foil", "Four Fingers"), 1512.0);
+    assert_eq!(score("9H 10H JH QH:Foil QH", "Four Fingers"), 1512.0);
+}
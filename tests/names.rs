@@ -0,0 +1,37 @@
+use ortalab::scoring::card::playing_card::Edition;
+use ortalab::scoring::joker::joker_card::{Joker, JokerCard};
+use ortalab::tarot::tarot_card::Tarot;
+
+// checks how joker and tarot names are matched, including close misspellings of jokers
+
+fn joker(name: &str) -> Option<Joker> {
+    name.parse().ok()
+}
+
+#[test]
+fn close_misspellings_of_one_joker_are_taken_as_it() {
+    assert_eq!(joker("Jolyl Joker"), Some(Joker::JollyJoker));
+    assert_eq!(joker("sokc and buskin"), Some(Joker::SockAndBuskin));
+    assert_eq!(joker("j_bluprint"), Some(Joker::Blueprint));
+    assert_eq!(
+        "Bluprint Foil".parse::<JokerCard>().ok(),
+        Some(JokerCard::new(Joker::Blueprint, Some(Edition::Foil)))
+    );
+}
+
+#[test]
+fn short_or_distant_names_are_not_guessed() {
+    // one letter off Mad Joker's key, but too short to allow a mistake
+    assert_eq!(joker("bad"), None);
+    // two letters off Baron, but only one mistake is allowed in five letters
+    assert_eq!(joker("Baorn"), None);
+    assert_eq!(joker("Not A Joker"), None);
+}
+
+#[test]
+fn tarots_are_matched_ignoring_case() {
+    assert_eq!("the star".parse::<Tarot>(), Ok(Tarot::TheStar));
+    assert_eq!("STRENGTH".parse::<Tarot>(), Ok(Tarot::Strength));
+    assert_eq!(" death ".parse::<Tarot>(), Ok(Tarot::Death));
+    assert!("The Fool".parse::<Tarot>().is_err());
+}
@@ -0,0 +1,292 @@
+use ortalab::scoring::card::playing_card::{Card, Edition, Enhancement};
+use ortalab::scoring::joker::joker_wrappers::JokerEffectFlags;
+use ortalib::{PokerHand, Rank, Suit};
+
+// File contains a slow reference evaluator for hand detection.
+// it tries every subset of up to five played cards without Stone cards and every suit..
+// a Wild card could take, and checks straights against every possible run of ranks. it..
+// shares no code with the real evaluator, so the two can be compared on random hands
+
+const SUITS: [Suit; 4] = [Suit::Spades, Suit::Hearts, Suit::Clubs, Suit::Diamonds];
+
+const RANKS: [Rank; 13] = [
+    Rank::Two,
+    Rank::Three,
+    Rank::Four,
+    Rank::Five,
+    Rank::Six,
+    Rank::Seven,
+    Rank::Eight,
+    Rank::Nine,
+    Rank::Ten,
+    Rank::Jack,
+    Rank::Queen,
+    Rank::King,
+    Rank::Ace,
+];
+
+// value of rank with Ace high, 2 to 14
+fn rank_value(rank: Rank) -> usize {
+    RANKS.iter().position(|&other| other == rank).unwrap() + 2
+}
+
+// every suit each card could be, one for normal cards and four for Wild cards
+fn suit_assignments(cards: &[Card]) -> Vec<Vec<Suit>> {
+    let mut assignments = vec![vec![]];
+
+    for card in cards {
+        let options: Vec<Suit> = if card.enhancement == Some(Enhancement::Wild) {
+            SUITS.to_vec()
+        } else {
+            vec![card.suit]
+        };
+
+        assignments = assignments
+            .into_iter()
+            .flat_map(|assignment| {
+                options.iter().map(move |&suit| {
+                    let mut next = assignment.clone();
+                    next.push(suit);
+                    next
+                })
+            })
+            .collect();
+    }
+
+    assignments
+}
+
+fn is_flush(suits: &[Suit], flags: &JokerEffectFlags, needed: usize) -> bool {
+    suits.len() >= needed
+        && suits.iter().all(|suit| {
+            if flags.smeared_joker {
+                suit.color() == suits[0].color()
+            } else {
+                *suit == suits[0]
+            }
+        })
+}
+
+// every run of values from 1 (low Ace) to 14 (high Ace) with length cards, where each..
+// step is one rank, or up to two ranks with Shortcut
+fn runs(length: usize, shortcut: bool) -> Vec<Vec<usize>> {
+    let steps: &[usize] = if shortcut { &[1, 2] } else { &[1] };
+    let mut runs: Vec<Vec<usize>> = (1..=14).map(|start| vec![start]).collect();
+
+    for _ in 1..length {
+        runs = runs
+            .into_iter()
+            .flat_map(|run| {
+                steps.iter().filter_map(move |step| {
+                    let next = run.last().unwrap() + step;
+                    (next <= 14).then(|| {
+                        let mut longer = run.clone();
+                        longer.push(next);
+                        longer
+                    })
+                })
+            })
+            .collect();
+    }
+
+    runs
+}
+
+fn is_straight(cards: &[Card], flags: &JokerEffectFlags, needed: usize) -> bool {
+    if cards.len() < needed {
+        return false;
+    }
+
+    // every way of counting each Ace as high or low
+    let aces = cards.iter().filter(|card| card.rank == Rank::Ace).count();
+    let runs = runs(cards.len(), flags.shortcut);
+
+    (0..1 << aces).any(|low_aces: usize| {
+        let mut ace_index = 0;
+        let mut values: Vec<usize> = cards
+            .iter()
+            .map(|card| {
+                if card.rank != Rank::Ace {
+                    return rank_value(card.rank);
+                }
+                ace_index += 1;
+                if low_aces & (1 << (ace_index - 1)) != 0 {
+                    1
+                } else {
+                    14
+                }
+            })
+            .collect();
+        values.sort();
+
+        runs.contains(&values)
+    })
+}
+
+// every hand made by all of cards, with each card taking one of suits
+fn classify(cards: &[Card], suits: &[Suit], flags: &JokerEffectFlags) -> Vec<PokerHand> {
+    let needed = if flags.four_fingers { 4 } else { 5 };
+
+    let mut counts = [0usize; 15];
+    for card in cards {
+        counts[rank_value(card.rank)] += 1;
+    }
+    let mut groups: Vec<usize> = counts.into_iter().filter(|&count| count > 0).collect();
+    groups.sort();
+    groups.reverse();
+
+    let flush = is_flush(suits, flags, needed);
+    let straight = is_straight(cards, flags, needed);
+    let five = groups == [5];
+    let full_house = groups == [3, 2];
+
+    let candidates = [
+        (five && flush, PokerHand::FlushFive),
+        (full_house && flush, PokerHand::FlushHouse),
+        (five, PokerHand::FiveOfAKind),
+        (straight && flush, PokerHand::StraightFlush),
+        (groups == [4], PokerHand::FourOfAKind),
+        (full_house, PokerHand::FullHouse),
+        (flush, PokerHand::Flush),
+        (straight, PokerHand::Straight),
+        (groups == [3], PokerHand::ThreeOfAKind),
+        (groups == [2, 2], PokerHand::TwoPair),
+        (groups == [2], PokerHand::Pair),
+        (groups == [1], PokerHand::HighCard),
+    ];
+
+    candidates
+        .into_iter()
+        .filter(|(made, _)| *made)
+        .map(|(_, hand)| hand)
+        .collect()
+}
+
+// every group of up to five of cards, in the order find_best_hand tries them. Stone..
+// cards have no rank or suit, so groups with one make no hand and are left out
+fn subsets(cards: &[Card]) -> impl Iterator<Item = Vec<Card>> {
+    (1usize..1 << cards.len())
+        .filter(|mask| mask.count_ones() <= 5)
+        .map(|mask| {
+            (0..cards.len())
+                .filter(|index| mask & (1 << index) != 0)
+                .map(|index| cards[index])
+                .collect::<Vec<Card>>()
+        })
+        .filter(|subset| {
+            subset
+                .iter()
+                .all(|card| card.enhancement != Some(Enhancement::Stone))
+        })
+}
+
+// every hand made by subset under some suit assignment of its Wild cards
+fn hands_made(subset: &[Card], flags: &JokerEffectFlags) -> Vec<PokerHand> {
+    suit_assignments(subset)
+        .iter()
+        .flat_map(|suits| classify(subset, suits, flags))
+        .collect()
+}
+
+// every hand made by some group of cards
+pub fn oracle_contained_hands(cards: &[Card], flags: &JokerEffectFlags) -> Vec<PokerHand> {
+    let mut hands: Vec<PokerHand> = subsets(cards)
+        .flat_map(|subset| hands_made(&subset, flags))
+        .collect();
+    hands.sort();
+    hands.dedup();
+    hands
+}
+
+// best hand among cards and the cards that make it. ties between groups of cards go to..
+// the most cards, then the most chips from ranks, then the highest ranks compared from..
+// the highest card down, then the most chips, added mult and multiplied mult from..
+// enhancements, editions and bonus chips, then the earliest group
+pub fn oracle_best_hand(cards: &[Card], flags: &JokerEffectFlags) -> (PokerHand, Vec<Card>) {
+    let mut best: Option<(OracleKey, Vec<Card>)> = None;
+
+    for subset in subsets(cards) {
+        let Some(hand) = hands_made(&subset, flags).into_iter().max() else {
+            continue;
+        };
+
+        let chips = subset.iter().map(|card| card.rank.rank_value()).sum();
+        let mut ranks: Vec<usize> = subset.iter().map(|card| rank_value(card.rank)).collect();
+        ranks.sort();
+        ranks.reverse();
+
+        let key = OracleKey {
+            hand,
+            size: subset.len(),
+            chips,
+            ranks,
+            extra_chips: subset.iter().map(extra_chips).sum(),
+            added_mult: subset.iter().map(added_mult).sum(),
+            mult_factor: subset.iter().map(mult_factor).product(),
+        };
+        if best.as_ref().is_none_or(|(best_key, _)| key > *best_key) {
+            best = Some((key, subset));
+        }
+    }
+
+    best.map_or((PokerHand::HighCard, vec![]), |(key, cards)| {
+        (key.hand, cards)
+    })
+}
+
+// chips card scores besides its rank
+fn extra_chips(card: &Card) -> f64 {
+    let bonus = if card.enhancement == Some(Enhancement::Bonus) {
+        30.0
+    } else {
+        0.0
+    };
+    let foil = if card.edition == Some(Edition::Foil) {
+        50.0
+    } else {
+        0.0
+    };
+    f64::from(card.bonus_chips) + bonus + foil
+}
+
+// mult card adds when it scores
+fn added_mult(card: &Card) -> f64 {
+    let mult = if card.enhancement == Some(Enhancement::Mult) {
+        4.0
+    } else {
+        0.0
+    };
+    let holographic = if card.edition == Some(Edition::Holographic) {
+        10.0
+    } else {
+        0.0
+    };
+    mult + holographic
+}
+
+// what card multiplies mult by when it scores
+fn mult_factor(card: &Card) -> f64 {
+    let glass = if card.enhancement == Some(Enhancement::Glass) {
+        2.0
+    } else {
+        1.0
+    };
+    let polychrome = if card.edition == Some(Edition::Polychrome) {
+        1.5
+    } else {
+        1.0
+    };
+    glass * polychrome
+}
+
+// what groups of cards are compared by, in order
+#[derive(PartialEq, PartialOrd)]
+struct OracleKey {
+    hand: PokerHand,
+    size: usize,
+    chips: f64,
+    ranks: Vec<usize>,
+    extra_chips: f64,
+    added_mult: f64,
+    mult_factor: f64,
+}
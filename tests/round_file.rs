@@ -0,0 +1,35 @@
+use ortalab::error::OrtalabError;
+use ortalab::validation::diagnostic::Diagnostic;
+use ortalab::validation::round_file::validate_round_source;
+
+// checks where problems in a round file are reported
+
+fn diagnostics(source: &str) -> Vec<Diagnostic> {
+    match validate_round_source(source, "round.yml") {
+        Err(OrtalabError::Parse(error)) => error.diagnostics,
+        Err(error) => panic!("expected a parse error, got {error}"),
+        Ok(_) => panic!("expected a parse error"),
+    }
+}
+
+#[test]
+fn problems_point_into_the_entry_they_were_found_in() {
+    let found = diagnostics("# ♦♦♦\ncards_played:\n  - \"K♦ Mlut\"\n");
+    assert_eq!((found[0].line, found[0].column), (3, 9));
+    assert_eq!(found[0].suggestion.as_deref(), Some("Mult"));
+}
+
+#[test]
+fn escaped_entries_are_reported_where_they_can_be_found() {
+    // the entry reads as K♦ but is written with an escape, so it can't be found in the..
+    // source and is reported at its field. offsets within it are left out, since they..
+    // could land inside a ♦ of the source
+    let found = diagnostics("# ♦♦♦\ncards_played:\n  - \"K\\u2666 Mlut\"\n");
+    assert_eq!(found[0].line, 2);
+    assert_eq!(found[0].suggestion.as_deref(), Some("Mult"));
+
+    let found = diagnostics("#♦♦♦\n{cards_played: [\"K\\u2666 Mlut\"]}\n");
+    assert_eq!(found[0].suggestion.as_deref(), Some("Mult"));
+    let found = diagnostics("{\"cards_played\": [\"K\\u2666      Mlut\"]}");
+    assert_eq!(found[0].suggestion.as_deref(), Some("Mult"));
+}
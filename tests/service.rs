@@ -0,0 +1,48 @@
+use ortalab::service::http::Request;
+use ortalab::service::routes::route;
+use ortalab::service::stream::run_stream;
+
+// checks the scoring service's routes without opening a socket, and --stream on..
+// in-memory input
+
+fn post(path: &str, body: String) -> u16 {
+    let request = Request {
+        method: "POST".to_string(),
+        path: path.to_string(),
+        body,
+    };
+    route(&request).status
+}
+
+// a round with one played card and held_count held cards
+fn round_json(held_count: usize) -> String {
+    let held = vec!["\"2S\""; held_count];
+    format!(
+        r#"{{"cards_played": ["AS"], "cards_held_in_hand": [{}], "jokers": []}}"#,
+        held.join(", ")
+    )
+}
+
+#[test]
+fn optimize_rejects_hands_too_large_to_search() {
+    assert_eq!(post("/optimize", round_json(15)), 200);
+    assert_eq!(post("/optimize", round_json(16)), 422);
+    assert_eq!(post("/optimize", round_json(100)), 422);
+}
+
+#[test]
+fn stream_keeps_going_after_a_bad_line() {
+    let input = "{\"cards_played\": [\"K\\u2666      Mlut\"]}\n\n{\"cards_played\": [\"AS\"]}\n";
+    let mut output = vec![];
+    run_stream(input.as_bytes(), &mut output).expect("in-memory output");
+
+    let lines: Vec<serde_json::Value> = String::from_utf8(output)
+        .expect("UTF-8 output")
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("one JSON object per line"))
+        .collect();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["error"]["kind"], "parse");
+    assert_eq!(lines[0]["error"]["diagnostics"][0]["suggestion"], "Mult");
+    assert_eq!(lines[1]["score"], 16.0);
+}
@@ -0,0 +1,48 @@
+use ortalab::scoring::card::playing_card::Enhancement;
+use ortalab::scoring::scorer::{calculate_score, identify_hand};
+use ortalab::tarot::tarot_card::Tarot;
+use ortalab::validation::round_flags::{parse_card_list, validate_round_flags};
+use ortalib::PokerHand;
+
+// checks that Stone cards score without a rank or suit, and that The Tower makes them
+
+fn score(play: &str, hold: Option<&str>, jokers: Option<&str>) -> f64 {
+    let round = validate_round_flags(play, hold, jokers).expect("valid round");
+    let (chips, mult) = calculate_score(round);
+    (chips * mult).floor()
+}
+
+#[test]
+fn stone_cards_always_score_but_make_no_hand() {
+    let round = validate_round_flags("KS KH KD:Stone", None, None).expect("valid round");
+    let (hand, cards) = identify_hand(&round.cards_played, &round.jokers);
+    assert_eq!(hand, PokerHand::Pair);
+    assert_eq!(cards.len(), 2);
+
+    // Pair 10 chips, two Kings 20 and the Stone card 50, x2 mult
+    assert_eq!(score("KS KH KD:Stone", None, None), 160.0);
+    // a lone Stone card is a High Card with no cards, and still scores
+    assert_eq!(score("2C:Stone", None, None), 55.0);
+}
+
+#[test]
+fn stone_cards_have_no_rank_or_suit_for_jokers() {
+    // Greedy Joker and Scary Face ignore a Stone King of Diamonds
+    assert_eq!(
+        score("AS KD:Stone", None, Some("Greedy Joker,Scary Face")),
+        score("AS KD:Stone", None, None)
+    );
+    // Baron ignores a held Stone King
+    assert_eq!(
+        score("AS", Some("KD:Stone"), Some("Baron")),
+        score("AS", Some("KD:Stone"), None)
+    );
+}
+
+#[test]
+fn the_tower_turns_a_card_into_stone() {
+    let mut hand = parse_card_list("hand", "AS KD").expect("valid cards");
+    Tarot::TheTower.apply(&mut hand, &[1]).expect("one target");
+    assert_eq!(hand[1].enhancement, Some(Enhancement::Stone));
+    assert_eq!(hand[0].enhancement, None);
+}
@@ -0,0 +1,46 @@
+use ortalab::error::OrtalabError;
+use ortalab::search::parallel::SearchOptions;
+use ortalab::tarot::target_search::{
+    MAX_TARGET_ALLOCATIONS, allocation_count, search_tarot_targets,
+};
+use ortalab::tarot::tarot_card::Tarot;
+use ortalab::validation::round_flags::parse_card_list;
+use ortalib::PokerHand;
+
+// checks that the tarot target search tries every legal target set
+
+#[test]
+fn death_can_copy_the_left_card_onto_the_right() {
+    // the best use turns the King into a third Ace, which needs the right card copied..
+    // from the left
+    let hand = parse_card_list("hand", "AS AH KD").expect("valid cards");
+    let result = search_tarot_targets(&hand, &[], &[Tarot::Death], &SearchOptions::default())
+        .expect("a small search")
+        .expect("a play");
+
+    assert_eq!(result.tarot_uses[0].targets, [2, 0]);
+    assert_eq!(result.best_play.score.hand, PokerHand::ThreeOfAKind);
+}
+
+#[test]
+fn the_tower_is_searched_like_any_other_tarot() {
+    let hand = parse_card_list("hand", "2S 3H").expect("valid cards");
+    let result = search_tarot_targets(&hand, &[], &[Tarot::TheTower], &SearchOptions::default())
+        .expect("a small search")
+        .expect("a play");
+
+    // a Stone card's 50 chips beat any rank in the hand
+    assert_eq!(result.best_play.score.score, 55.0 + 3.0);
+}
+
+#[test]
+fn searches_with_too_many_allocations_are_refused() {
+    let hand = parse_card_list("hand", "AS AH KD QC JS 10H 9D 8C").expect("valid cards");
+    // each Death has 56 ordered pairs of 8 cards
+    let tarots = [Tarot::Death, Tarot::Death, Tarot::Death];
+    assert_eq!(allocation_count(hand.len(), &tarots), 56 * 56 * 56);
+    assert!(allocation_count(hand.len(), &tarots) > MAX_TARGET_ALLOCATIONS);
+
+    let result = search_tarot_targets(&hand, &[], &tarots, &SearchOptions::default());
+    assert!(matches!(result, Err(OrtalabError::InvalidArgument(_))));
+}